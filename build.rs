@@ -0,0 +1,4 @@
+// Re-embed `migrations/` (sqlx::migrate!) whenever a migration is added or edited
+fn main() {
+    println!("cargo:rerun-if-changed=migrations");
+}
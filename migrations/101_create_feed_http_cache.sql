@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS warehouse.feed_http_cache (
+    feed TEXT PRIMARY KEY,
+    etag TEXT,
+    last_modified TEXT,
+    not_modified_count BIGINT NOT NULL DEFAULT 0,
+    last_not_modified_at TIMESTAMP WITH TIME ZONE,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
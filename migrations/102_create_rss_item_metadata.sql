@@ -0,0 +1,22 @@
+ALTER TABLE warehouse.rss_items ADD COLUMN IF NOT EXISTS guid TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_rss_items_source_guid ON warehouse.rss_items (source, guid);
+
+CREATE TABLE IF NOT EXISTS warehouse.rss_item_authors (
+    item_id UUID NOT NULL REFERENCES warehouse.rss_items (id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    PRIMARY KEY (item_id, position)
+);
+
+CREATE INDEX IF NOT EXISTS idx_rss_item_authors_name ON warehouse.rss_item_authors (name);
+
+CREATE TABLE IF NOT EXISTS warehouse.rss_item_categories (
+    item_id UUID NOT NULL REFERENCES warehouse.rss_items (id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    domain TEXT,
+    PRIMARY KEY (item_id, position)
+);
+
+CREATE INDEX IF NOT EXISTS idx_rss_item_categories_name ON warehouse.rss_item_categories (name);
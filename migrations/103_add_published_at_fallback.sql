@@ -0,0 +1,3 @@
+-- True when the feed gave no usable date and published_at is the first-seen time
+ALTER TABLE warehouse.rss_items
+    ADD COLUMN IF NOT EXISTS published_at_fallback BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,12 @@
+-- Let identity backfills re-key rss_items without orphaning metadata rows
+ALTER TABLE warehouse.rss_item_authors
+    DROP CONSTRAINT IF EXISTS rss_item_authors_item_id_fkey,
+    ADD CONSTRAINT rss_item_authors_item_id_fkey
+        FOREIGN KEY (item_id) REFERENCES warehouse.rss_items (id)
+        ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE warehouse.rss_item_categories
+    DROP CONSTRAINT IF EXISTS rss_item_categories_item_id_fkey,
+    ADD CONSTRAINT rss_item_categories_item_id_fkey
+        FOREIGN KEY (item_id) REFERENCES warehouse.rss_items (id)
+        ON DELETE CASCADE ON UPDATE CASCADE;
@@ -0,0 +1,26 @@
+ALTER TABLE warehouse.rss_items
+    ADD COLUMN IF NOT EXISTS content_hash TEXT,
+    ADD COLUMN IF NOT EXISTS revision_count INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS last_updated_at TIMESTAMP WITH TIME ZONE;
+
+-- Same digest as RssItem::content_hash: title, link and summary joined by 0x1f
+UPDATE warehouse.rss_items
+SET content_hash = encode(
+    sha256(convert_to(title || chr(31) || link || chr(31) || COALESCE(summary, ''), 'UTF8')),
+    'hex'
+)
+WHERE content_hash IS NULL;
+
+-- Prior versions of an item; `revision` 0 is the originally ingested one
+CREATE TABLE IF NOT EXISTS warehouse.rss_item_revisions (
+    item_id UUID NOT NULL REFERENCES warehouse.rss_items (id) ON DELETE CASCADE ON UPDATE CASCADE,
+    revision INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    link TEXT NOT NULL,
+    summary TEXT,
+    published_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    content_hash TEXT NOT NULL,
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    superseded_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (item_id, revision)
+);
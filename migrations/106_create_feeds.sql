@@ -0,0 +1,17 @@
+-- Feed registry managed with `raven-news feeds ...`; rows override config
+-- file / built-in feeds of the same name and are reloaded every cycle
+CREATE TABLE IF NOT EXISTS warehouse.feeds (
+    name TEXT PRIMARY KEY,
+    url TEXT NOT NULL,
+    parser TEXT NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT true,
+    interval_secs INTEGER CHECK (interval_secs > 0),
+    options JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+-- Feed that produced the item (NULL for rows ingested before this column)
+ALTER TABLE warehouse.rss_items ADD COLUMN IF NOT EXISTS feed TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_rss_items_feed ON warehouse.rss_items (feed);
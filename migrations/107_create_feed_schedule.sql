@@ -0,0 +1,12 @@
+-- Per-feed polling state written by the `run` scheduler, so intervals
+-- survive restarts and `stats schedule` can show the next poll
+CREATE TABLE IF NOT EXISTS warehouse.feed_schedule (
+    feed TEXT PRIMARY KEY,
+    interval_secs INTEGER NOT NULL CHECK (interval_secs > 0),
+    adaptive BOOLEAN NOT NULL DEFAULT false,
+    -- Publish cadence observed at the last poll (adaptive feeds)
+    cadence_secs INTEGER,
+    last_run_at TIMESTAMP WITH TIME ZONE,
+    next_run_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
@@ -0,0 +1,25 @@
+-- One row per poll of a feed (retries of transient errors included in
+-- `attempts`), written by the ingest module for auditing
+CREATE TABLE IF NOT EXISTS warehouse.fetch_runs (
+    id BIGSERIAL PRIMARY KEY,
+    feed TEXT NOT NULL,
+    started_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    finished_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    -- Status of the last response; NULL when no response arrived
+    http_status INTEGER,
+    bytes BIGINT,
+    not_modified BOOLEAN NOT NULL DEFAULT false,
+    -- Skipped because the feed's circuit breaker was open
+    short_circuited BOOLEAN NOT NULL DEFAULT false,
+    parsed INTEGER NOT NULL DEFAULT 0,
+    inserted INTEGER NOT NULL DEFAULT 0,
+    updated INTEGER NOT NULL DEFAULT 0,
+    error TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_fetch_runs_feed_started_at
+    ON warehouse.fetch_runs (feed, started_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_fetch_runs_started_at
+    ON warehouse.fetch_runs (started_at);
@@ -0,0 +1,22 @@
+-- Raw response bodies, gzip-compressed and keyed by their SHA-256, for
+-- `[ingest.archive] storage = "postgres"`
+CREATE TABLE IF NOT EXISTS warehouse.raw_payloads (
+    sha256 TEXT PRIMARY KEY,
+    size_bytes BIGINT NOT NULL,
+    gzip BYTEA NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+-- Which feed returned which payload when; the body itself lives in
+-- `raw_payloads` or under `ingest.archive.dir`, depending on `storage`
+CREATE TABLE IF NOT EXISTS warehouse.feed_payloads (
+    id BIGSERIAL PRIMARY KEY,
+    feed TEXT NOT NULL,
+    fetched_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    sha256 TEXT NOT NULL,
+    storage TEXT NOT NULL CHECK (storage IN ('postgres', 'disk')),
+    size_bytes BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_feed_payloads_feed_fetched_at
+    ON warehouse.feed_payloads (feed, fetched_at);
@@ -0,0 +1,5 @@
+-- Parse warnings per poll: entries skipped for a missing field, unparseable
+-- dates and undecodable text (a JSON array of `ParseDiagnostic`s)
+ALTER TABLE warehouse.fetch_runs
+    ADD COLUMN IF NOT EXISTS skipped INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS diagnostics JSONB;
@@ -0,0 +1,8 @@
+-- Encoding each body was decoded from (WHATWG name, e.g. `Shift_JIS`).
+-- Archived payloads are now the raw bytes, so a reparse needs it to decode
+-- them the same way; older payloads (NULL) were archived as UTF-8.
+ALTER TABLE warehouse.fetch_runs
+    ADD COLUMN IF NOT EXISTS encoding TEXT;
+
+ALTER TABLE warehouse.feed_payloads
+    ADD COLUMN IF NOT EXISTS encoding TEXT;
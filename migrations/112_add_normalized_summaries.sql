@@ -0,0 +1,6 @@
+-- Normalized versions of `summary`, derived at ingestion: plain text
+-- (entities decoded, tags stripped, whitespace collapsed, length capped)
+-- and sanitized HTML (scripts, frames and tracking pixels removed)
+ALTER TABLE warehouse.rss_items
+    ADD COLUMN IF NOT EXISTS summary_text TEXT,
+    ADD COLUMN IF NOT EXISTS summary_html TEXT;
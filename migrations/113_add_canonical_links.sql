@@ -0,0 +1,8 @@
+-- `link` keeps the link as published; `canonical_link` is the absolute form
+-- without fragment and tracking parameters, set at ingestion. Rows from
+-- before this column get it on their next poll.
+ALTER TABLE warehouse.rss_items ADD COLUMN IF NOT EXISTS canonical_link TEXT;
+
+-- One row per article and source, whatever URL variant it arrived under
+CREATE UNIQUE INDEX IF NOT EXISTS idx_rss_items_source_canonical_link
+    ON warehouse.rss_items (source, canonical_link);
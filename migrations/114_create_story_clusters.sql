@@ -0,0 +1,23 @@
+-- Near-duplicate items across feeds and sources, grouped into stories.
+-- Only stories reported by more than one item get a cluster.
+CREATE TABLE IF NOT EXISTS warehouse.story_clusters (
+    id UUID PRIMARY KEY,
+    item_count INTEGER NOT NULL DEFAULT 0,
+    first_published_at TIMESTAMP WITH TIME ZONE,
+    last_published_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_story_clusters_last_published_at
+    ON warehouse.story_clusters (last_published_at);
+
+-- Signatures of the normalized title + summary (see rss::signature):
+-- a 64-bit SimHash and 32 big-endian MinHash values. NULL until clustered.
+ALTER TABLE warehouse.rss_items
+    ADD COLUMN IF NOT EXISTS simhash BIGINT,
+    ADD COLUMN IF NOT EXISTS minhash BYTEA,
+    ADD COLUMN IF NOT EXISTS cluster_id UUID
+        REFERENCES warehouse.story_clusters (id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_rss_items_cluster_id ON warehouse.rss_items (cluster_id);
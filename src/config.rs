@@ -0,0 +1,770 @@
+use crate::error::ConfigError;
+use crate::ingest::{Feed, default_feeds};
+use crate::rss::date::DateFallback;
+use crate::rss::identity::IdentityStrategy;
+use crate::rss::link::{LinkCanonicalizer, TRACKING_PARAMS};
+use crate::rss::{
+    AutoDetectParser, RssParser, atom::AtomParser, bloomberg::BloombergRssParser,
+    coindesk::CoindeskRssParser, generic::GenericRssParser, json_feed::JsonFeedParser,
+    reuters::ReutersRssParser,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Parser implementation attached to a feed (`parser = "..."` in the config file)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParserKind {
+    Bloomberg,
+    Coindesk,
+    Reuters,
+    /// Plain RSS 2.0, stored under `options.source` (or the feed name)
+    Rss,
+    /// Atom 1.0, stored under `options.source` (or the feed name)
+    Atom,
+    /// JSON Feed 1.x, stored under `options.source` (or the feed name)
+    JsonFeed,
+    /// Sniff each payload and pick the matching generic parser
+    Auto,
+}
+
+impl ParserKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "bloomberg" => Some(Self::Bloomberg),
+            "coindesk" => Some(Self::Coindesk),
+            "reuters" => Some(Self::Reuters),
+            "rss" => Some(Self::Rss),
+            "atom" => Some(Self::Atom),
+            "json_feed" | "jsonfeed" => Some(Self::JsonFeed),
+            "auto" => Some(Self::Auto),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Bloomberg => "bloomberg",
+            Self::Coindesk => "coindesk",
+            Self::Reuters => "reuters",
+            Self::Rss => "rss",
+            Self::Atom => "atom",
+            Self::JsonFeed => "json_feed",
+            Self::Auto => "auto",
+        }
+    }
+
+    /// Source label hard-coded by the publisher parsers
+    pub fn fixed_source(&self) -> Option<&'static str> {
+        match self {
+            Self::Bloomberg | Self::Coindesk | Self::Reuters => Some(self.as_str()),
+            Self::Rss | Self::Atom | Self::JsonFeed | Self::Auto => None,
+        }
+    }
+
+    pub fn build(&self, source: &str) -> Box<dyn RssParser> {
+        match self {
+            Self::Bloomberg => Box::new(BloombergRssParser),
+            Self::Coindesk => Box::new(CoindeskRssParser),
+            Self::Reuters => Box::new(ReutersRssParser),
+            Self::Rss => Box::new(GenericRssParser::new(source)),
+            Self::Atom => Box::new(AtomParser::new(source)),
+            Self::JsonFeed => Box::new(JsonFeedParser::new(source)),
+            Self::Auto => Box::new(AutoDetectParser::new(source)),
+        }
+    }
+}
+
+/// Optional per-feed settings (`[feeds.options]` table)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeedOptions {
+    /// HTTP request timeout in seconds
+    pub timeout_secs: Option<u64>,
+    /// Overrides the default `User-Agent` header
+    pub user_agent: Option<String>,
+    /// Source label stored on items by the generic (`rss`, `atom`, `json_feed`, `auto`) parsers
+    pub source: Option<String>,
+    /// Policy for items without a parseable publish date
+    #[serde(default)]
+    pub date_fallback: DateFallback,
+    /// How item IDs are derived for deduplication
+    #[serde(default)]
+    pub identity: IdentityStrategy,
+    /// Polling interval in seconds (the `interval_secs` column for registry feeds)
+    pub interval_secs: Option<u64>,
+    /// Overrides `ingest.schedule.adaptive` for this feed
+    pub adaptive: Option<bool>,
+    /// Fail the poll on any parse warning instead of storing what parsed
+    #[serde(default)]
+    pub strict: bool,
+}
+
+/// Scheduler-wide settings (`[ingest]` table)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IngestSettings {
+    /// Maximum number of feeds fetched at the same time
+    pub max_concurrency: usize,
+    /// Maximum number of concurrent requests against a single host
+    pub per_host_concurrency: usize,
+    /// Default HTTP request timeout in seconds
+    pub timeout_secs: u64,
+    pub retry: RetrySettings,
+    pub breaker: BreakerSettings,
+    pub schedule: ScheduleSettings,
+    pub archive: ArchiveSettings,
+    pub links: LinkSettings,
+    pub clusters: ClusterSettings,
+}
+
+impl Default for IngestSettings {
+    fn default() -> Self {
+        Self {
+            max_concurrency: 8,
+            per_host_concurrency: 2,
+            timeout_secs: 30,
+            retry: RetrySettings::default(),
+            breaker: BreakerSettings::default(),
+            schedule: ScheduleSettings::default(),
+            archive: ArchiveSettings::default(),
+            links: LinkSettings::default(),
+            clusters: ClusterSettings::default(),
+        }
+    }
+}
+
+/// Retries of transient errors within one cycle (`[ingest.retry]` table)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetrySettings {
+    /// Total attempts per feed and cycle, including the first one
+    pub max_attempts: u32,
+    pub base_delay_ms: u64,
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetrySettings {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay_ms: 500,
+            max_delay_ms: 10_000,
+        }
+    }
+}
+
+/// Backing off persistently failing feeds across cycles (`[ingest.breaker]` table)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BreakerSettings {
+    /// Consecutive failed cycles before the breaker opens
+    pub failure_threshold: u32,
+    /// Initial time a feed is skipped once the breaker opens
+    pub cooldown_secs: u64,
+    /// Upper bound for the cooldown, which doubles after each failed trial
+    pub max_cooldown_secs: u64,
+}
+
+impl Default for BreakerSettings {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 3,
+            cooldown_secs: 300,
+            max_cooldown_secs: 3600,
+        }
+    }
+}
+
+/// Polling cadence of the `run` scheduler (`[ingest.schedule]` table)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScheduleSettings {
+    /// Interval for feeds without `options.interval_secs`
+    pub interval_secs: u64,
+    /// Derive each feed's interval from its observed publish cadence
+    pub adaptive: bool,
+    /// Bounds for adaptive intervals
+    pub min_interval_secs: u64,
+    pub max_interval_secs: u64,
+}
+
+impl Default for ScheduleSettings {
+    fn default() -> Self {
+        Self {
+            interval_secs: 60,
+            adaptive: false,
+            min_interval_secs: 60,
+            max_interval_secs: 3600,
+        }
+    }
+}
+
+/// Where raw response bodies are archived
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveStorage {
+    #[default]
+    Off,
+    /// `warehouse.raw_payloads`
+    Postgres,
+    /// Files under `ingest.archive.dir`
+    Disk,
+}
+
+impl ArchiveStorage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Postgres => "postgres",
+            Self::Disk => "disk",
+        }
+    }
+}
+
+/// Archive of raw response bodies for `reparse` (`[ingest.archive]` table)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ArchiveSettings {
+    pub storage: ArchiveStorage,
+    /// Root directory for `storage = "disk"`; also needed to read back
+    /// payloads archived on disk
+    pub dir: Option<PathBuf>,
+}
+
+/// Canonical item links (`[ingest.links]` table)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LinkSettings {
+    /// Query parameters removed from links; `name*` matches a prefix.
+    /// Replaces the built-in tracking list.
+    pub strip_params: Vec<String>,
+}
+
+impl Default for LinkSettings {
+    fn default() -> Self {
+        Self {
+            strip_params: TRACKING_PARAMS.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+}
+
+impl LinkSettings {
+    pub fn canonicalizer(&self) -> LinkCanonicalizer {
+        LinkCanonicalizer::new(&self.strip_params)
+    }
+}
+
+/// Longest accepted `ingest.clusters.window_hours` (30 days)
+pub const MAX_CLUSTER_WINDOW_HOURS: u64 = 24 * 30;
+
+/// Grouping near-duplicate items into stories (`[ingest.clusters]` table)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClusterSettings {
+    /// Cluster new items at the end of every cycle
+    pub enabled: bool,
+    /// Items further apart in publish time are never the same story
+    pub window_hours: u64,
+    /// Estimated Jaccard similarity (MinHash) at which two items match
+    pub min_similarity: f64,
+    /// SimHash bit distance at or below which two items match regardless;
+    /// below 64, the width of the hash
+    pub max_distance: u32,
+}
+
+impl Default for ClusterSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window_hours: 36,
+            min_similarity: 0.5,
+            max_distance: 3,
+        }
+    }
+}
+
+/// Resolved runtime configuration
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub ingest: IngestSettings,
+    pub feeds: Vec<Feed>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            ingest: IngestSettings::default(),
+            feeds: default_feeds(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    #[serde(default)]
+    ingest: IngestSettings,
+    #[serde(default)]
+    feeds: Vec<FeedEntry>,
+}
+
+/// An unvalidated feed definition: a `[[feeds]]` entry or a `warehouse.feeds` row
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeedEntry {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub parser: String,
+    #[serde(default = "default_active")]
+    pub active: bool,
+    #[serde(default)]
+    pub options: FeedOptions,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+/// Resolve the runtime configuration.
+/// Falls back to the built-in feed list when no config path is given.
+pub fn load_config(path: Option<&Path>) -> Result<Config, ConfigError> {
+    match path {
+        Some(path) => {
+            let content = fs::read_to_string(path).map_err(|source| ConfigError::Io {
+                path: path.display().to_string(),
+                source,
+            })?;
+            parse_config(&content)
+        }
+        None => Ok(Config::default()),
+    }
+}
+
+/// Parse and validate a `feeds.toml` document
+pub fn parse_config(content: &str) -> Result<Config, ConfigError> {
+    let file: ConfigFile = toml::from_str(content)?;
+
+    if file.ingest.max_concurrency == 0 {
+        return Err(ConfigError::InvalidSetting {
+            key: "ingest.max_concurrency",
+            reason: "must be positive".to_string(),
+        });
+    }
+    if file.ingest.per_host_concurrency == 0 {
+        return Err(ConfigError::InvalidSetting {
+            key: "ingest.per_host_concurrency",
+            reason: "must be positive".to_string(),
+        });
+    }
+    if file.ingest.timeout_secs == 0 {
+        return Err(ConfigError::InvalidSetting {
+            key: "ingest.timeout_secs",
+            reason: "must be positive".to_string(),
+        });
+    }
+    if file.ingest.retry.max_attempts == 0 {
+        return Err(ConfigError::InvalidSetting {
+            key: "ingest.retry.max_attempts",
+            reason: "must be positive".to_string(),
+        });
+    }
+    if file.ingest.breaker.failure_threshold == 0 {
+        return Err(ConfigError::InvalidSetting {
+            key: "ingest.breaker.failure_threshold",
+            reason: "must be positive".to_string(),
+        });
+    }
+    if file.ingest.breaker.max_cooldown_secs < file.ingest.breaker.cooldown_secs {
+        return Err(ConfigError::InvalidSetting {
+            key: "ingest.breaker.max_cooldown_secs",
+            reason: "must not be smaller than `cooldown_secs`".to_string(),
+        });
+    }
+
+    if file.ingest.schedule.interval_secs == 0 {
+        return Err(ConfigError::InvalidSetting {
+            key: "ingest.schedule.interval_secs",
+            reason: "must be positive".to_string(),
+        });
+    }
+    if file.ingest.schedule.min_interval_secs == 0 {
+        return Err(ConfigError::InvalidSetting {
+            key: "ingest.schedule.min_interval_secs",
+            reason: "must be positive".to_string(),
+        });
+    }
+    if file.ingest.schedule.max_interval_secs < file.ingest.schedule.min_interval_secs {
+        return Err(ConfigError::InvalidSetting {
+            key: "ingest.schedule.max_interval_secs",
+            reason: "must not be smaller than `min_interval_secs`".to_string(),
+        });
+    }
+
+    if file.ingest.archive.storage == ArchiveStorage::Disk && file.ingest.archive.dir.is_none() {
+        return Err(ConfigError::InvalidSetting {
+            key: "ingest.archive.dir",
+            reason: "is required when `storage` is \"disk\"".to_string(),
+        });
+    }
+
+    if file
+        .ingest
+        .links
+        .strip_params
+        .iter()
+        .any(|p| matches!(p.trim(), "" | "*"))
+    {
+        return Err(ConfigError::InvalidSetting {
+            key: "ingest.links.strip_params",
+            reason: "entries must name a parameter or prefix".to_string(),
+        });
+    }
+
+    if !(1..=MAX_CLUSTER_WINDOW_HOURS).contains(&file.ingest.clusters.window_hours) {
+        return Err(ConfigError::InvalidSetting {
+            key: "ingest.clusters.window_hours",
+            reason: format!("must be between 1 and {MAX_CLUSTER_WINDOW_HOURS}"),
+        });
+    }
+    let similarity = file.ingest.clusters.min_similarity;
+    if !(similarity > 0.0 && similarity <= 1.0) {
+        return Err(ConfigError::InvalidSetting {
+            key: "ingest.clusters.min_similarity",
+            reason: "must be in (0, 1]".to_string(),
+        });
+    }
+    if file.ingest.clusters.max_distance >= u64::BITS {
+        return Err(ConfigError::InvalidSetting {
+            key: "ingest.clusters.max_distance",
+            reason: "must be below 64; every pair of hashes is within 64 bits".to_string(),
+        });
+    }
+
+    Ok(Config {
+        ingest: file.ingest,
+        feeds: validate_feeds(file.feeds)?,
+    })
+}
+
+fn validate_feeds(entries: Vec<FeedEntry>) -> Result<Vec<Feed>, ConfigError> {
+    if entries.is_empty() {
+        return Err(ConfigError::Empty);
+    }
+
+    let mut feeds: Vec<Feed> = Vec::with_capacity(entries.len());
+
+    for (index, entry) in entries.into_iter().enumerate() {
+        let name = entry.name.clone();
+        let invalid = |reason: String| ConfigError::InvalidFeed {
+            index,
+            name: name.clone(),
+            reason,
+        };
+
+        let feed = entry.validate().map_err(invalid)?;
+        if feeds.iter().any(|f| f.name == feed.name) {
+            return Err(invalid("duplicate feed name".to_string()));
+        }
+        if let Some(reason) = identity_conflict(&feeds, &feed) {
+            return Err(invalid(reason));
+        }
+
+        feeds.push(feed);
+    }
+
+    Ok(feeds)
+}
+
+impl FeedEntry {
+    /// Check a single entry; the error is the reason shown to the user
+    pub fn validate(self) -> Result<Feed, String> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Err("`name` must not be empty".to_string());
+        }
+
+        match reqwest::Url::parse(&self.url) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+            Ok(url) => return Err(format!("unsupported URL scheme '{}'", url.scheme())),
+            Err(e) => return Err(format!("invalid URL '{}': {e}", self.url)),
+        }
+
+        let parser = ParserKind::from_name(&self.parser)
+            .ok_or_else(|| format!("unknown parser '{}'", self.parser))?;
+
+        if self
+            .options
+            .source
+            .as_deref()
+            .is_some_and(|s| s.trim().is_empty())
+        {
+            return Err("`options.source` must not be empty".to_string());
+        }
+        if self.options.timeout_secs == Some(0) {
+            return Err("`options.timeout_secs` must be positive".to_string());
+        }
+        if self.options.interval_secs == Some(0) {
+            return Err("`options.interval_secs` must be positive".to_string());
+        }
+
+        Ok(Feed {
+            name: name.to_string(),
+            url: self.url,
+            parser,
+            active: self.active,
+            options: self.options,
+        })
+    }
+}
+
+/// IDs are scoped by source, so feeds sharing one must agree on the
+/// identity strategy; returns the reason when `feed` disagrees with `feeds`
+pub fn identity_conflict<'a>(
+    feeds: impl IntoIterator<Item = &'a Feed>,
+    feed: &Feed,
+) -> Option<String> {
+    let identity = feed.options.identity;
+    feeds
+        .into_iter()
+        .find(|other| {
+            other.name != feed.name
+                && other.source() == feed.source()
+                && other.options.identity != identity
+        })
+        .map(|other| {
+            format!(
+                "`options.identity` '{}' conflicts with '{}' used by feed '{}' of source '{}'",
+                identity.as_str(),
+                other.options.identity.as_str(),
+                other.name,
+                feed.source()
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let content = r#"
+            [ingest]
+            max_concurrency = 4
+            per_host_concurrency = 1
+
+            [[feeds]]
+            name = "bloomberg_markets"
+            url = "https://feeds.bloomberg.com/markets/news.rss"
+            parser = "bloomberg"
+
+            [[feeds]]
+            name = "reuters_events"
+            url = "https://ir.thomsonreuters.com/rss/events.xml?items=15"
+            parser = "reuters"
+            active = false
+
+            [feeds.options]
+            timeout_secs = 10
+        "#;
+
+        let config = parse_config(content).expect("Failed to parse config");
+        assert_eq!(config.ingest.max_concurrency, 4);
+        assert_eq!(config.ingest.per_host_concurrency, 1);
+        assert_eq!(config.ingest.timeout_secs, 30);
+        assert_eq!(config.ingest.schedule.interval_secs, 60);
+        assert!(!config.ingest.schedule.adaptive);
+
+        let feeds = config.feeds;
+        assert_eq!(feeds.len(), 2);
+        assert_eq!(feeds[0].parser, ParserKind::Bloomberg);
+        assert!(feeds[0].active);
+        assert!(!feeds[1].active);
+        assert_eq!(feeds[1].options.timeout_secs, Some(10));
+    }
+
+    #[test]
+    fn test_parse_config_names_invalid_entry() {
+        let content = r#"
+            [[feeds]]
+            name = "coindesk"
+            url = "https://www.coindesk.com/arc/outboundfeeds/rss"
+            parser = "coindesk"
+
+            [[feeds]]
+            name = "broken"
+            url = "https://example.com/rss"
+            parser = "nope"
+        "#;
+
+        let err = parse_config(content).unwrap_err();
+        match err {
+            ConfigError::InvalidFeed { index, name, .. } => {
+                assert_eq!(index, 1);
+                assert_eq!(name, "broken");
+            }
+            other => panic!("Unexpected error: {other}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_config_rejects_conflicting_identity() {
+        let content = r#"
+            [[feeds]]
+            name = "bloomberg_markets"
+            url = "https://feeds.bloomberg.com/markets/news.rss"
+            parser = "bloomberg"
+
+            [feeds.options]
+            identity = "guid"
+
+            [[feeds]]
+            name = "bloomberg_wealth"
+            url = "https://feeds.bloomberg.com/wealth/news.rss"
+            parser = "bloomberg"
+        "#;
+
+        assert!(matches!(
+            parse_config(content),
+            Err(ConfigError::InvalidFeed { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_config_rejects_invalid_values() {
+        let duplicate = r#"
+            [[feeds]]
+            name = "coindesk"
+            url = "https://www.coindesk.com/arc/outboundfeeds/rss"
+            parser = "coindesk"
+
+            [[feeds]]
+            name = "coindesk"
+            url = "https://www.coindesk.com/arc/outboundfeeds/rss"
+            parser = "coindesk"
+        "#;
+        assert!(matches!(
+            parse_config(duplicate),
+            Err(ConfigError::InvalidFeed { index: 1, .. })
+        ));
+
+        let bad_url = r#"
+            [[feeds]]
+            name = "coindesk"
+            url = "ftp://www.coindesk.com/rss"
+            parser = "coindesk"
+        "#;
+        assert!(matches!(
+            parse_config(bad_url),
+            Err(ConfigError::InvalidFeed { index: 0, .. })
+        ));
+
+        let zero_concurrency = r#"
+            [ingest]
+            max_concurrency = 0
+
+            [[feeds]]
+            name = "coindesk"
+            url = "https://www.coindesk.com/arc/outboundfeeds/rss"
+            parser = "coindesk"
+        "#;
+        assert!(matches!(
+            parse_config(zero_concurrency),
+            Err(ConfigError::InvalidSetting {
+                key: "ingest.max_concurrency",
+                ..
+            })
+        ));
+
+        let inverted_bounds = r#"
+            [ingest.schedule]
+            min_interval_secs = 600
+            max_interval_secs = 60
+
+            [[feeds]]
+            name = "coindesk"
+            url = "https://www.coindesk.com/arc/outboundfeeds/rss"
+            parser = "coindesk"
+        "#;
+        assert!(matches!(
+            parse_config(inverted_bounds),
+            Err(ConfigError::InvalidSetting {
+                key: "ingest.schedule.max_interval_secs",
+                ..
+            })
+        ));
+
+        let strip_everything = r#"
+            [ingest.links]
+            strip_params = ["utm_*", "*"]
+
+            [[feeds]]
+            name = "coindesk"
+            url = "https://www.coindesk.com/arc/outboundfeeds/rss"
+            parser = "coindesk"
+        "#;
+        assert!(matches!(
+            parse_config(strip_everything),
+            Err(ConfigError::InvalidSetting {
+                key: "ingest.links.strip_params",
+                ..
+            })
+        ));
+
+        for (clusters, key) in [
+            ("window_hours = 0", "ingest.clusters.window_hours"),
+            (
+                "window_hours = 9223372036854775807",
+                "ingest.clusters.window_hours",
+            ),
+            ("max_distance = 64", "ingest.clusters.max_distance"),
+        ] {
+            let content = format!(
+                r#"
+                [ingest.clusters]
+                {clusters}
+
+                [[feeds]]
+                name = "coindesk"
+                url = "https://www.coindesk.com/arc/outboundfeeds/rss"
+                parser = "coindesk"
+            "#
+            );
+            assert!(
+                matches!(
+                    parse_config(&content),
+                    Err(ConfigError::InvalidSetting { key: k, .. }) if k == key
+                ),
+                "{clusters}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_generic_rss_feed_is_a_config_entry() {
+        let content = r#"
+            [[feeds]]
+            name = "fed_press"
+            url = "https://www.federalreserve.gov/feeds/press_all.xml"
+            parser = "rss"
+
+            [feeds.options]
+            source = "federal_reserve"
+        "#;
+
+        let config = parse_config(content).expect("Failed to parse config");
+        let feed = &config.feeds[0];
+        assert_eq!(feed.parser, ParserKind::Rss);
+        assert_eq!(feed.source(), "federal_reserve");
+    }
+
+    #[test]
+    fn test_load_config_defaults_to_builtin() {
+        let config = load_config(None).expect("Built-in feeds must load");
+        assert!(config.feeds.iter().any(|f| f.name == "coindesk"));
+    }
+}
@@ -0,0 +1,521 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+/// An item that has not been clustered yet
+#[derive(Debug, Clone)]
+pub struct UnclusteredItem {
+    pub id: Uuid,
+    pub title: String,
+    /// `summary_text`, else the raw summary
+    pub summary: Option<String>,
+    pub published_at: DateTime<Utc>,
+}
+
+/// A clustered item, as a match candidate for new ones
+#[derive(Debug, Clone)]
+pub struct SignedItem {
+    pub id: Uuid,
+    pub published_at: DateTime<Utc>,
+    pub cluster_id: Option<Uuid>,
+    pub simhash: i64,
+    pub minhash: Vec<u8>,
+}
+
+/// Signature and story of one item, written by `save_clustering`
+#[derive(Debug, Clone)]
+pub struct ItemClustering {
+    pub id: Uuid,
+    /// `None` for an item signed earlier that only joins a cluster
+    pub signature: Option<(i64, Vec<u8>)>,
+    pub cluster_id: Option<Uuid>,
+}
+
+/// A `warehouse.story_clusters` row
+#[derive(Debug, Clone)]
+pub struct StoryCluster {
+    pub id: Uuid,
+    pub item_count: i32,
+    pub first_published_at: Option<DateTime<Utc>>,
+    pub last_published_at: Option<DateTime<Utc>>,
+    /// Title of the earliest item
+    pub title: Option<String>,
+    pub sources: Vec<String>,
+}
+
+/// An item of a story cluster
+#[derive(Debug, Clone)]
+pub struct ClusterItem {
+    pub id: Uuid,
+    pub source: String,
+    pub feed: Option<String>,
+    pub title: String,
+    pub link: String,
+    pub published_at: DateTime<Utc>,
+}
+
+/// Items published since `since` that have no signature yet, oldest first
+pub async fn list_unclustered_items(
+    pool: &PgPool,
+    since: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<UnclusteredItem>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, title, COALESCE(summary_text, summary) AS summary, published_at
+        FROM warehouse.rss_items
+        WHERE minhash IS NULL AND published_at >= $1
+        ORDER BY published_at, id
+        LIMIT $2
+        "#,
+        since,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| UnclusteredItem {
+            id: r.id,
+            title: r.title,
+            summary: r.summary,
+            published_at: r.published_at,
+        })
+        .collect())
+}
+
+/// Items with a signature published in `[since, until]`
+pub async fn list_signed_items(
+    pool: &PgPool,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Result<Vec<SignedItem>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, published_at, cluster_id, simhash AS "simhash!", minhash AS "minhash!"
+        FROM warehouse.rss_items
+        WHERE minhash IS NOT NULL AND simhash IS NOT NULL
+          AND published_at BETWEEN $1 AND $2
+        ORDER BY published_at, id
+        "#,
+        since,
+        until
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| SignedItem {
+            id: r.id,
+            published_at: r.published_at,
+            cluster_id: r.cluster_id,
+            simhash: r.simhash,
+            minhash: r.minhash,
+        })
+        .collect())
+}
+
+/// Store signatures and cluster assignments in one transaction, creating
+/// new clusters and refreshing the counts of the touched ones
+pub async fn save_clustering(pool: &PgPool, items: &[ItemClustering]) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let clustered: Vec<&ItemClustering> = items.iter().filter(|i| i.cluster_id.is_some()).collect();
+    let cluster_ids: Vec<Uuid> = clustered.iter().filter_map(|i| i.cluster_id).collect();
+    sqlx::query!(
+        r#"
+        INSERT INTO warehouse.story_clusters (id)
+        SELECT DISTINCT * FROM UNNEST($1::UUID[])
+        ON CONFLICT (id) DO NOTHING
+        "#,
+        &cluster_ids
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // Items before clusters and both in ID order, as in `insert_rss_items`
+    let mut locked: Vec<Uuid> = items.iter().map(|i| i.id).collect();
+    locked.sort();
+    sqlx::query!(
+        r#"
+        SELECT id FROM warehouse.rss_items
+        WHERE id = ANY($1)
+        ORDER BY id
+        FOR UPDATE
+        "#,
+        &locked
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let (mut ids, mut simhashes, mut minhashes) = (vec![], vec![], vec![]);
+    for item in items {
+        if let Some((simhash, minhash)) = &item.signature {
+            ids.push(item.id);
+            simhashes.push(*simhash);
+            minhashes.push(minhash.clone());
+        }
+    }
+    sqlx::query!(
+        r#"
+        UPDATE warehouse.rss_items AS i
+        SET simhash = u.simhash, minhash = u.minhash
+        FROM UNNEST($1::UUID[], $2::BIGINT[], $3::BYTEA[]) AS u(id, simhash, minhash)
+        WHERE i.id = u.id
+        "#,
+        &ids,
+        &simhashes,
+        &minhashes
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let item_ids: Vec<Uuid> = clustered.iter().map(|i| i.id).collect();
+    sqlx::query!(
+        r#"
+        UPDATE warehouse.rss_items AS i
+        SET cluster_id = u.cluster_id
+        FROM UNNEST($1::UUID[], $2::UUID[]) AS u(id, cluster_id)
+        WHERE i.id = u.id
+        "#,
+        &item_ids,
+        &cluster_ids
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    refresh_clusters(&mut tx, &cluster_ids).await?;
+    tx.commit().await
+}
+
+/// Recount the items of the given clusters and delete the ones left empty.
+/// The clusters are locked in ID order first, so concurrent writers can't
+/// deadlock on them.
+pub(crate) async fn refresh_clusters(
+    conn: &mut PgConnection,
+    cluster_ids: &[Uuid],
+) -> Result<(), sqlx::Error> {
+    if cluster_ids.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query!(
+        r#"
+        SELECT id FROM warehouse.story_clusters
+        WHERE id = ANY($1)
+        ORDER BY id
+        FOR UPDATE
+        "#,
+        cluster_ids
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE warehouse.story_clusters AS c
+        SET item_count = s.item_count,
+            first_published_at = s.first_published_at,
+            last_published_at = s.last_published_at,
+            updated_at = now()
+        FROM (
+            SELECT cluster_id, COUNT(*)::INTEGER AS item_count,
+                   MIN(published_at) AS first_published_at,
+                   MAX(published_at) AS last_published_at
+            FROM warehouse.rss_items
+            WHERE cluster_id = ANY($1)
+            GROUP BY cluster_id
+        ) AS s
+        WHERE c.id = s.cluster_id
+        "#,
+        cluster_ids
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        DELETE FROM warehouse.story_clusters AS c
+        WHERE c.id = ANY($1)
+          AND NOT EXISTS (SELECT 1 FROM warehouse.rss_items WHERE cluster_id = c.id)
+        "#,
+        cluster_ids
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Clusters with at least `min_items` items whose latest item is from
+/// `since` on, newest first
+pub async fn list_story_clusters(
+    pool: &PgPool,
+    since: DateTime<Utc>,
+    min_items: i32,
+) -> Result<Vec<StoryCluster>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT c.id, c.item_count, c.first_published_at, c.last_published_at,
+               (SELECT title FROM warehouse.rss_items
+                WHERE cluster_id = c.id ORDER BY published_at, id LIMIT 1) AS title,
+               ARRAY(SELECT DISTINCT source FROM warehouse.rss_items
+                     WHERE cluster_id = c.id ORDER BY source) AS "sources!"
+        FROM warehouse.story_clusters AS c
+        WHERE c.last_published_at >= $1 AND c.item_count >= $2
+        ORDER BY c.last_published_at DESC, c.id
+        "#,
+        since,
+        min_items
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| StoryCluster {
+            id: r.id,
+            item_count: r.item_count,
+            first_published_at: r.first_published_at,
+            last_published_at: r.last_published_at,
+            title: r.title,
+            sources: r.sources,
+        })
+        .collect())
+}
+
+/// The cluster `id`, or the cluster of the item `id`, with its items in
+/// publish order
+pub async fn get_story_cluster(
+    pool: &PgPool,
+    id: Uuid,
+) -> Result<Option<(StoryCluster, Vec<ClusterItem>)>, sqlx::Error> {
+    let Some(row) = sqlx::query!(
+        r#"
+        SELECT c.id, c.item_count, c.first_published_at, c.last_published_at
+        FROM warehouse.story_clusters AS c
+        WHERE c.id = $1
+           OR c.id = (SELECT cluster_id FROM warehouse.rss_items WHERE id = $1)
+        "#,
+        id
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let items: Vec<ClusterItem> = sqlx::query!(
+        r#"
+        SELECT id, source, feed, title, link, published_at
+        FROM warehouse.rss_items
+        WHERE cluster_id = $1
+        ORDER BY published_at, id
+        "#,
+        row.id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|r| ClusterItem {
+        id: r.id,
+        source: r.source,
+        feed: r.feed,
+        title: r.title,
+        link: r.link,
+        published_at: r.published_at,
+    })
+    .collect();
+
+    let mut sources: Vec<String> = items.iter().map(|i| i.source.clone()).collect();
+    sources.sort();
+    sources.dedup();
+    let cluster = StoryCluster {
+        id: row.id,
+        item_count: row.item_count,
+        first_published_at: row.first_published_at,
+        last_published_at: row.last_published_at,
+        title: items.first().map(|i| i.title.clone()),
+        sources,
+    };
+    Ok(Some((cluster, items)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{UpsertOutcome, create_pg_pool, insert_rss_items, upsert_rss_item};
+    use crate::rss::RssItem;
+    use crate::rss::signature::StorySignature;
+    use chrono::TimeDelta;
+    use dotenvy::dotenv;
+
+    #[tokio::test]
+    async fn test_save_clustering_and_show_cluster() {
+        dotenv().ok();
+
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = create_pg_pool(&database_url).await;
+
+        let tag = Uuid::new_v4();
+        let now = Utc::now();
+        let items = vec![
+            RssItem::new(
+                format!("wire_{tag}"),
+                "Fed holds rates",
+                "https://wire.example.com/fed",
+                None,
+                Some(now - TimeDelta::hours(1)),
+            ),
+            RssItem::new(
+                format!("desk_{tag}"),
+                "Fed holds rates steady",
+                "https://desk.example.com/fed",
+                None,
+                Some(now),
+            ),
+        ];
+        insert_rss_items(&pool, &items).await.unwrap();
+
+        let cluster_id = Uuid::new_v4();
+        let signature = StorySignature::new("Fed holds rates", None);
+        let clustering: Vec<ItemClustering> = items
+            .iter()
+            .map(|item| ItemClustering {
+                id: item.id,
+                signature: Some((signature.simhash as i64, signature.minhash_bytes())),
+                cluster_id: Some(cluster_id),
+            })
+            .collect();
+        save_clustering(&pool, &clustering).await.unwrap();
+
+        let signed = list_signed_items(&pool, now - TimeDelta::hours(1), now)
+            .await
+            .unwrap();
+        let stored = signed.iter().find(|s| s.id == items[0].id).unwrap();
+        assert_eq!(stored.cluster_id, Some(cluster_id));
+        assert_eq!(
+            StorySignature::from_parts(stored.simhash as u64, &stored.minhash),
+            signature
+        );
+
+        // Found by its own ID or by the ID of any member
+        let (cluster, members) = get_story_cluster(&pool, items[1].id)
+            .await
+            .unwrap()
+            .expect("cluster of the item");
+        assert_eq!(cluster.id, cluster_id);
+        assert_eq!(cluster.item_count, 2);
+        assert_eq!(cluster.title.as_deref(), Some("Fed holds rates"));
+        assert_eq!(
+            cluster.sources,
+            vec![format!("desk_{tag}"), format!("wire_{tag}")]
+        );
+        assert_eq!(members[1].id, items[1].id);
+
+        let listed = list_story_clusters(&pool, now - TimeDelta::minutes(1), 2)
+            .await
+            .unwrap();
+        assert!(
+            listed
+                .iter()
+                .any(|c| c.id == cluster_id && c.item_count == 2)
+        );
+        assert!(
+            get_story_cluster(&pool, Uuid::new_v4())
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_revision_leaves_cluster_until_signed_again() {
+        dotenv().ok();
+
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = create_pg_pool(&database_url).await;
+
+        let tag = Uuid::new_v4();
+        let now = Utc::now();
+        let items: Vec<RssItem> = ["wire", "desk"]
+            .iter()
+            .map(|desk| {
+                RssItem::new(
+                    format!("{desk}_{tag}"),
+                    "Fed holds rates",
+                    format!("https://{desk}.example.com/fed"),
+                    None,
+                    Some(now),
+                )
+            })
+            .collect();
+        insert_rss_items(&pool, &items).await.unwrap();
+        let (original, other) = (&items[0], &items[1]);
+
+        let cluster_id = Uuid::new_v4();
+        let signature = StorySignature::new("Fed holds rates", None);
+        let clustering: Vec<ItemClustering> = items
+            .iter()
+            .map(|item| ItemClustering {
+                id: item.id,
+                signature: Some((signature.simhash as i64, signature.minhash_bytes())),
+                cluster_id: Some(cluster_id),
+            })
+            .collect();
+        save_clustering(&pool, &clustering).await.unwrap();
+
+        // A corrected headline under the same ID
+        let mut corrected = RssItem::new(
+            format!("wire_{tag}"),
+            "Bitcoin tops $100,000",
+            "https://wire.example.com/fed",
+            None,
+            Some(now),
+        );
+        corrected.id = original.id;
+        assert_eq!(
+            upsert_rss_item(&pool, &corrected).await.unwrap(),
+            UpsertOutcome::Updated { revision_count: 1 }
+        );
+
+        let pending = list_unclustered_items(&pool, now - TimeDelta::minutes(1), i64::MAX)
+            .await
+            .unwrap();
+        let revised = pending
+            .iter()
+            .find(|i| i.id == original.id)
+            .expect("revised item is signed again");
+        assert_eq!(revised.title, "Bitcoin tops $100,000");
+
+        let (cluster, members) = get_story_cluster(&pool, cluster_id)
+            .await
+            .unwrap()
+            .expect("cluster of the other item");
+        assert_eq!(cluster.item_count, 1);
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].id, other.id);
+
+        // The next pass signs it with the new text
+        let resigned = StorySignature::new(&revised.title, revised.summary.as_deref());
+        save_clustering(
+            &pool,
+            &[ItemClustering {
+                id: original.id,
+                signature: Some((resigned.simhash as i64, resigned.minhash_bytes())),
+                cluster_id: None,
+            }],
+        )
+        .await
+        .unwrap();
+        let signed = list_signed_items(&pool, now, now).await.unwrap();
+        let stored = signed.iter().find(|s| s.id == original.id).unwrap();
+        assert_eq!(stored.cluster_id, None);
+        assert_eq!(
+            StorySignature::from_parts(stored.simhash as u64, &stored.minhash),
+            resigned
+        );
+    }
+}
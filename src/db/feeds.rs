@@ -0,0 +1,146 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+/// A `warehouse.feeds` row; `options` holds the serialized `FeedOptions`
+#[derive(Debug, Clone)]
+pub struct FeedRecord {
+    pub name: String,
+    pub url: String,
+    pub parser: String,
+    pub active: bool,
+    pub interval_secs: Option<i32>,
+    pub options: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Fields written by `insert_feed`; timestamps are set by the database
+#[derive(Debug, Clone)]
+pub struct NewFeedRecord {
+    pub name: String,
+    pub url: String,
+    pub parser: String,
+    pub active: bool,
+    pub interval_secs: Option<i32>,
+    pub options: serde_json::Value,
+}
+
+pub async fn list_feeds(pool: &PgPool) -> Result<Vec<FeedRecord>, sqlx::Error> {
+    sqlx::query_as!(
+        FeedRecord,
+        r#"
+        SELECT name, url, parser, active, interval_secs, options, created_at, updated_at
+        FROM warehouse.feeds
+        ORDER BY name
+        "#
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn get_feed(pool: &PgPool, name: &str) -> Result<Option<FeedRecord>, sqlx::Error> {
+    sqlx::query_as!(
+        FeedRecord,
+        r#"
+        SELECT name, url, parser, active, interval_secs, options, created_at, updated_at
+        FROM warehouse.feeds
+        WHERE name = $1
+        "#,
+        name
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Returns false when a feed with the same name is already registered
+pub async fn insert_feed(pool: &PgPool, feed: &NewFeedRecord) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO warehouse.feeds (name, url, parser, active, interval_secs, options)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (name) DO NOTHING
+        "#,
+        feed.name,
+        feed.url,
+        feed.parser,
+        feed.active,
+        feed.interval_secs,
+        feed.options,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Returns false when no feed with that name is registered
+pub async fn set_feed_active(pool: &PgPool, name: &str, active: bool) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE warehouse.feeds
+        SET active = $2, updated_at = now()
+        WHERE name = $1
+        "#,
+        name,
+        active
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Returns false when no feed with that name is registered
+pub async fn delete_feed(pool: &PgPool, name: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!("DELETE FROM warehouse.feeds WHERE name = $1", name)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::create_pg_pool;
+    use dotenvy::dotenv;
+
+    #[tokio::test]
+    async fn test_feed_registry_crud() {
+        dotenv().ok();
+
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = create_pg_pool(&database_url).await;
+
+        let name = format!("feed_{}", uuid::Uuid::new_v4());
+        let feed = NewFeedRecord {
+            name: name.clone(),
+            url: "https://example.com/rss".to_string(),
+            parser: "rss".to_string(),
+            active: true,
+            interval_secs: Some(300),
+            options: serde_json::json!({ "source": "example" }),
+        };
+
+        assert!(insert_feed(&pool, &feed).await.unwrap());
+        assert!(!insert_feed(&pool, &feed).await.unwrap());
+
+        assert!(set_feed_active(&pool, &name, false).await.unwrap());
+        let stored = get_feed(&pool, &name).await.unwrap().unwrap();
+        assert!(!stored.active);
+        assert_eq!(stored.interval_secs, Some(300));
+        assert_eq!(stored.options["source"], "example");
+        assert!(
+            list_feeds(&pool)
+                .await
+                .unwrap()
+                .iter()
+                .any(|f| f.name == name)
+        );
+
+        assert!(delete_feed(&pool, &name).await.unwrap());
+        assert!(!delete_feed(&pool, &name).await.unwrap());
+        assert!(!set_feed_active(&pool, &name, true).await.unwrap());
+        assert!(get_feed(&pool, &name).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,225 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+/// A `warehouse.fetch_runs` row: one poll of one feed
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchRun {
+    pub feed: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    /// HTTP attempts, including retries; 0 when the breaker skipped the feed
+    pub attempts: i32,
+    pub http_status: Option<i32>,
+    pub bytes: Option<i64>,
+    /// Encoding the body was decoded from
+    pub encoding: Option<String>,
+    pub not_modified: bool,
+    pub short_circuited: bool,
+    pub parsed: i32,
+    /// Entries the parser dropped for lacking a required field
+    pub skipped: i32,
+    /// The poll's `ParseDiagnostic`s as a JSON array; `None` when clean
+    pub diagnostics: Option<serde_json::Value>,
+    pub inserted: i32,
+    pub updated: i32,
+    pub error: Option<String>,
+}
+
+impl FetchRun {
+    pub fn duration_ms(&self) -> i64 {
+        (self.finished_at - self.started_at).num_milliseconds()
+    }
+
+    pub fn is_failure(&self) -> bool {
+        self.error.is_some()
+    }
+}
+
+/// Per-feed summary of the runs in a time window
+#[derive(Debug, Clone)]
+pub struct FeedRunStats {
+    pub feed: String,
+    pub runs: i64,
+    pub failures: i64,
+    pub not_modified: i64,
+    pub inserted: i64,
+    pub avg_duration_ms: Option<f64>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+pub async fn record_fetch_run(pool: &PgPool, run: &FetchRun) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO warehouse.fetch_runs (
+            feed, started_at, finished_at, attempts, http_status, bytes, encoding,
+            not_modified, short_circuited, parsed, skipped, diagnostics,
+            inserted, updated, error
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+        "#,
+        run.feed,
+        run.started_at,
+        run.finished_at,
+        run.attempts,
+        run.http_status,
+        run.bytes,
+        run.encoding,
+        run.not_modified,
+        run.short_circuited,
+        run.parsed,
+        run.skipped,
+        run.diagnostics,
+        run.inserted,
+        run.updated,
+        run.error,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Runs started in `[since, until)`, oldest first; every feed when `feed` is `None`
+pub async fn list_fetch_runs(
+    pool: &PgPool,
+    feed: Option<&str>,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Result<Vec<FetchRun>, sqlx::Error> {
+    sqlx::query_as!(
+        FetchRun,
+        r#"
+        SELECT feed, started_at, finished_at, attempts, http_status, bytes, encoding,
+               not_modified, short_circuited, parsed, skipped, diagnostics,
+               inserted, updated, error
+        FROM warehouse.fetch_runs
+        WHERE ($1::TEXT IS NULL OR feed = $1)
+          AND started_at >= $2
+          AND started_at < $3
+        ORDER BY started_at, id
+        "#,
+        feed,
+        since,
+        until
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// One summary row per feed polled since `since`
+pub async fn feed_run_stats(
+    pool: &PgPool,
+    since: DateTime<Utc>,
+) -> Result<Vec<FeedRunStats>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            feed,
+            COUNT(*) AS "runs!",
+            COUNT(*) FILTER (WHERE error IS NOT NULL) AS "failures!",
+            COUNT(*) FILTER (WHERE not_modified) AS "not_modified!",
+            COALESCE(SUM(inserted), 0) AS "inserted!",
+            AVG(EXTRACT(EPOCH FROM finished_at - started_at) * 1000)::FLOAT8 AS avg_duration_ms,
+            MAX(started_at) AS last_run_at,
+            MAX(started_at) FILTER (WHERE error IS NULL AND NOT short_circuited) AS last_success_at,
+            (ARRAY_AGG(error ORDER BY started_at DESC) FILTER (WHERE error IS NOT NULL))[1] AS last_error
+        FROM warehouse.fetch_runs
+        WHERE started_at >= $1
+        GROUP BY feed
+        ORDER BY feed
+        "#,
+        since
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| FeedRunStats {
+            feed: r.feed,
+            runs: r.runs,
+            failures: r.failures,
+            not_modified: r.not_modified,
+            inserted: r.inserted,
+            avg_duration_ms: r.avg_duration_ms,
+            last_run_at: r.last_run_at,
+            last_success_at: r.last_success_at,
+            last_error: r.last_error,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::create_pg_pool;
+    use chrono::{Duration, SubsecRound};
+    use dotenvy::dotenv;
+
+    #[tokio::test]
+    async fn test_record_and_list_fetch_runs() {
+        dotenv().ok();
+
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = create_pg_pool(&database_url).await;
+
+        let feed = format!("runs_{}", uuid::Uuid::new_v4());
+        let start = Utc::now().trunc_subsecs(6);
+        let ok = FetchRun {
+            feed: feed.clone(),
+            started_at: start,
+            finished_at: start + Duration::milliseconds(250),
+            attempts: 1,
+            http_status: Some(200),
+            bytes: Some(4096),
+            encoding: Some("Shift_JIS".to_string()),
+            not_modified: false,
+            short_circuited: false,
+            parsed: 20,
+            skipped: 1,
+            diagnostics: Some(serde_json::json!([
+                { "item_index": 4, "byte_offset": 812, "kind": "missing_field", "field": "link" }
+            ])),
+            inserted: 3,
+            updated: 1,
+            error: None,
+        };
+        let failed = FetchRun {
+            started_at: start + Duration::minutes(1),
+            finished_at: start + Duration::minutes(1) + Duration::seconds(2),
+            attempts: 3,
+            http_status: Some(503),
+            bytes: None,
+            encoding: None,
+            parsed: 0,
+            skipped: 0,
+            diagnostics: None,
+            inserted: 0,
+            updated: 0,
+            error: Some("HTTP request failed with status 503".to_string()),
+            ..ok.clone()
+        };
+        record_fetch_run(&pool, &ok).await.unwrap();
+        record_fetch_run(&pool, &failed).await.unwrap();
+
+        let runs = list_fetch_runs(&pool, Some(&feed), start, start + Duration::minutes(5))
+            .await
+            .unwrap();
+        assert_eq!(runs, vec![ok.clone(), failed.clone()]);
+        assert_eq!(runs[0].duration_ms(), 250);
+
+        // The window is half-open
+        let first = list_fetch_runs(&pool, Some(&feed), start, failed.started_at)
+            .await
+            .unwrap();
+        assert_eq!(first, vec![ok]);
+
+        let stats = feed_run_stats(&pool, start).await.unwrap();
+        let stats = stats.iter().find(|s| s.feed == feed).unwrap();
+        assert_eq!((stats.runs, stats.failures, stats.inserted), (2, 1, 3));
+        assert_eq!(stats.last_success_at, Some(start));
+        assert_eq!(stats.last_error, failed.error);
+    }
+}
@@ -0,0 +1,116 @@
+use sqlx::PgPool;
+
+/// HTTP validators remembered from the last successful fetch of a feed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeedValidators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl FeedValidators {
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+pub async fn get_feed_validators(pool: &PgPool, feed: &str) -> Result<FeedValidators, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT etag, last_modified FROM warehouse.feed_http_cache
+        WHERE feed = $1
+        "#,
+        feed
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row
+        .map(|r| FeedValidators {
+            etag: r.etag,
+            last_modified: r.last_modified,
+        })
+        .unwrap_or_default())
+}
+
+pub async fn save_feed_validators(
+    pool: &PgPool,
+    feed: &str,
+    validators: &FeedValidators,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO warehouse.feed_http_cache (feed, etag, last_modified)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (feed) DO UPDATE
+        SET etag = EXCLUDED.etag,
+            last_modified = EXCLUDED.last_modified,
+            updated_at = now()
+        "#,
+        feed,
+        validators.etag,
+        validators.last_modified,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn record_not_modified(pool: &PgPool, feed: &str) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE warehouse.feed_http_cache
+        SET not_modified_count = not_modified_count + 1,
+            last_not_modified_at = now()
+        WHERE feed = $1
+        "#,
+        feed
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::create_pg_pool;
+    use dotenvy::dotenv;
+
+    #[tokio::test]
+    async fn test_save_feed_validators_round_trip() {
+        dotenv().ok();
+
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = create_pg_pool(&database_url).await;
+
+        let feed = format!("http_cache_{}", uuid::Uuid::new_v4());
+        assert!(get_feed_validators(&pool, &feed).await.unwrap().is_empty());
+
+        let mut validators = FeedValidators {
+            etag: Some("\"v1\"".to_string()),
+            last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string()),
+        };
+        save_feed_validators(&pool, &feed, &validators)
+            .await
+            .unwrap();
+        assert_eq!(get_feed_validators(&pool, &feed).await.unwrap(), validators);
+
+        // A response without Last-Modified clears the stored one
+        validators.etag = Some("W/\"v2\"".to_string());
+        validators.last_modified = None;
+        save_feed_validators(&pool, &feed, &validators)
+            .await
+            .unwrap();
+        assert_eq!(get_feed_validators(&pool, &feed).await.unwrap(), validators);
+
+        sqlx::query!(
+            "DELETE FROM warehouse.feed_http_cache WHERE feed = $1",
+            feed
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+}
@@ -0,0 +1,389 @@
+use crate::db::clusters::refresh_clusters;
+use crate::rss::RssItem;
+use crate::rss::identity::IdentityStrategy;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Outcome of re-keying one source's stored items
+#[derive(Debug, Clone, Default)]
+pub struct BackfillReport {
+    pub source: String,
+    pub scanned: usize,
+    /// Rows whose ID changed to the strategy's ID
+    pub rekeyed: usize,
+    /// Rows folded into another row with the same identity; their content
+    /// is kept as revisions
+    pub merged: usize,
+}
+
+/// Recompute the IDs of every stored item of `source` under `strategy`, so
+/// rows written under a previous strategy keep matching new polls.
+///
+/// Rows that collapse onto one identity (e.g. a headline stored before and
+/// after a correction) are merged into one row: it keeps the oldest ID,
+/// takes the newest content and records every other version in
+/// `rss_item_revisions`. With `dry_run` the transaction is rolled back and
+/// only the counts are reported.
+pub async fn backfill_item_ids(
+    pool: &PgPool,
+    source: &str,
+    strategy: IdentityStrategy,
+    dry_run: bool,
+) -> Result<BackfillReport, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, title, link, canonical_link, guid, published_at, published_at_fallback
+        FROM warehouse.rss_items
+        WHERE source = $1
+        ORDER BY created_at, id
+        FOR UPDATE
+        "#,
+        source
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let mut report = BackfillReport {
+        source: source.to_string(),
+        scanned: rows.len(),
+        ..Default::default()
+    };
+
+    // Target ID -> current IDs, oldest first
+    let mut groups: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for row in rows {
+        let mut item = RssItem::new(source, row.title, row.link, None, Some(row.published_at))
+            .with_guid(row.guid);
+        item.published_at_fallback = row.published_at_fallback;
+        item.canonical_link = row.canonical_link;
+
+        groups
+            .entry(strategy.item_id(&item))
+            .or_default()
+            .push(row.id);
+    }
+
+    for (target, ids) in groups {
+        let keeper = if ids.contains(&target) {
+            target
+        } else {
+            ids[0]
+        };
+
+        if ids.len() > 1 {
+            report.merged += ids.len() - 1;
+            merge_rows(&mut tx, keeper, &ids).await?;
+        }
+
+        if keeper != target {
+            report.rekeyed += 1;
+            // Authors, categories and revisions follow through ON UPDATE CASCADE
+            sqlx::query!(
+                "UPDATE warehouse.rss_items SET id = $1 WHERE id = $2",
+                target,
+                keeper
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    if dry_run {
+        tx.rollback().await?;
+    } else {
+        tx.commit().await?;
+    }
+
+    Ok(report)
+}
+
+/// A stored version of an item, current or superseded
+struct Version {
+    title: String,
+    link: String,
+    summary: Option<String>,
+    published_at: DateTime<Utc>,
+    content_hash: String,
+    recorded_at: DateTime<Utc>,
+}
+
+/// Fold the rows `ids` into `keeper`: the newest row's content and metadata
+/// move onto it, and all other versions of every row, their earlier
+/// revisions included, become its revisions in the order they were stored
+async fn merge_rows(
+    tx: &mut Transaction<'_, Postgres>,
+    keeper: Uuid,
+    ids: &[Uuid],
+) -> Result<(), sqlx::Error> {
+    let newest = sqlx::query!(
+        r#"
+        SELECT id, link, canonical_link, title, summary, summary_text, summary_html,
+               published_at, published_at_fallback, guid, content_hash, feed,
+               COALESCE(last_updated_at, created_at) AS "stored_at!", cluster_id
+        FROM warehouse.rss_items
+        WHERE id = ANY($1)
+        ORDER BY COALESCE(last_updated_at, created_at) DESC, created_at DESC, id
+        LIMIT 1
+        "#,
+        ids
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    let mut versions: Vec<Version> = sqlx::query_as!(
+        Version,
+        r#"
+        SELECT title AS "title!", link AS "link!", summary, published_at AS "published_at!",
+               content_hash AS "content_hash!", recorded_at AS "recorded_at!"
+        FROM (
+            SELECT title, link, summary, published_at, content_hash, recorded_at
+            FROM warehouse.rss_item_revisions
+            WHERE item_id = ANY($1)
+            UNION ALL
+            SELECT title, link, summary, published_at, COALESCE(content_hash, ''),
+                   COALESCE(last_updated_at, created_at)
+            FROM warehouse.rss_items
+            WHERE id = ANY($1) AND id <> $2
+        ) AS v
+        "#,
+        ids,
+        newest.id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+    versions.sort_by_key(|v| v.recorded_at);
+
+    // Each version was superseded when the next one was stored
+    let mut superseded_at: Vec<DateTime<Utc>> =
+        versions.iter().skip(1).map(|v| v.recorded_at).collect();
+    superseded_at.push(newest.stored_at);
+
+    let clusters: Vec<Uuid> = sqlx::query_scalar!(
+        r#"SELECT DISTINCT cluster_id AS "cluster_id!" FROM warehouse.rss_items WHERE id = ANY($1) AND cluster_id IS NOT NULL"#,
+        ids
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    sqlx::query!(
+        "DELETE FROM warehouse.rss_item_revisions WHERE item_id = ANY($1)",
+        ids
+    )
+    .execute(&mut **tx)
+    .await?;
+    if newest.id != keeper {
+        sqlx::query!(
+            "DELETE FROM warehouse.rss_item_authors WHERE item_id = $1",
+            keeper
+        )
+        .execute(&mut **tx)
+        .await?;
+        sqlx::query!(
+            "DELETE FROM warehouse.rss_item_categories WHERE item_id = $1",
+            keeper
+        )
+        .execute(&mut **tx)
+        .await?;
+        sqlx::query!(
+            "UPDATE warehouse.rss_item_authors SET item_id = $1 WHERE item_id = $2",
+            keeper,
+            newest.id
+        )
+        .execute(&mut **tx)
+        .await?;
+        sqlx::query!(
+            "UPDATE warehouse.rss_item_categories SET item_id = $1 WHERE item_id = $2",
+            keeper,
+            newest.id
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    let others: Vec<Uuid> = ids.iter().copied().filter(|id| *id != keeper).collect();
+    sqlx::query!(
+        "DELETE FROM warehouse.rss_items WHERE id = ANY($1)",
+        &others
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    let revisions: Vec<i32> = (0..versions.len() as i32).collect();
+    let (mut titles, mut links, mut summaries) = (vec![], vec![], vec![]);
+    let (mut published_at, mut hashes, mut recorded_at) = (vec![], vec![], vec![]);
+    for version in versions {
+        titles.push(version.title);
+        links.push(version.link);
+        summaries.push(version.summary);
+        published_at.push(version.published_at);
+        hashes.push(version.content_hash);
+        recorded_at.push(version.recorded_at);
+    }
+    sqlx::query!(
+        r#"
+        INSERT INTO warehouse.rss_item_revisions
+            (item_id, revision, title, link, summary, published_at, content_hash,
+             recorded_at, superseded_at)
+        SELECT $1, * FROM UNNEST(
+            $2::INTEGER[], $3::TEXT[], $4::TEXT[], $5::TEXT[], $6::TIMESTAMPTZ[],
+            $7::TEXT[], $8::TIMESTAMPTZ[], $9::TIMESTAMPTZ[]
+        )
+        "#,
+        keeper,
+        &revisions,
+        &titles,
+        &links,
+        &summaries as &[Option<String>],
+        &published_at,
+        &hashes,
+        &recorded_at,
+        &superseded_at,
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    // The merged text needs a new story signature
+    sqlx::query!(
+        r#"
+        UPDATE warehouse.rss_items
+        SET title = $2, link = $3, canonical_link = $4, summary = $5, summary_text = $6,
+            summary_html = $7, published_at = $8, published_at_fallback = $9, guid = $10,
+            content_hash = $11, feed = $12, revision_count = $13, last_updated_at = $14,
+            simhash = NULL, minhash = NULL, cluster_id = NULL
+        WHERE id = $1
+        "#,
+        keeper,
+        newest.title,
+        newest.link,
+        newest.canonical_link,
+        newest.summary,
+        newest.summary_text,
+        newest.summary_html,
+        newest.published_at,
+        newest.published_at_fallback,
+        newest.guid,
+        newest.content_hash,
+        newest.feed,
+        revisions.len() as i32,
+        newest.stored_at,
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    refresh_clusters(&mut *tx, &clusters).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{
+        UpsertOutcome, create_pg_pool, get_rss_item, insert_rss_item, item_revisions,
+        upsert_rss_item,
+    };
+    use chrono::{Duration, Utc};
+    use dotenvy::dotenv;
+
+    #[tokio::test]
+    async fn test_backfill_item_ids() {
+        dotenv().ok();
+
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = create_pg_pool(&database_url).await;
+
+        let source = format!("backfill_{}", Uuid::new_v4());
+        let published_at = Utc::now();
+        let original = RssItem::new(
+            &source,
+            "Fed hlods rates",
+            "https://e.com/fed",
+            None,
+            Some(published_at),
+        )
+        .with_guid(Some("fed-1".to_string()))
+        .with_authors(vec!["Desk".to_string()]);
+        let corrected = RssItem::new(
+            &source,
+            "Fed holds rates",
+            "https://e.com/fed",
+            None,
+            Some(published_at + Duration::minutes(5)),
+        )
+        .with_guid(Some("fed-1".to_string()))
+        .with_authors(vec!["Markets Desk".to_string()]);
+
+        // Stored under the legacy strategy: the typo got a summary as a
+        // revision, then the correction became a second row
+        assert!(insert_rss_item(&pool, &original).await.unwrap());
+        let mut summarized = RssItem::new(
+            &source,
+            "Fed hlods rates",
+            "https://e.com/fed",
+            Some("Rates unchanged".to_string()),
+            Some(published_at),
+        );
+        summarized.id = original.id;
+        assert_eq!(
+            upsert_rss_item(&pool, &summarized).await.unwrap(),
+            UpsertOutcome::Updated { revision_count: 1 }
+        );
+        assert!(insert_rss_item(&pool, &corrected).await.unwrap());
+
+        let dry = backfill_item_ids(&pool, &source, IdentityStrategy::Guid, true)
+            .await
+            .unwrap();
+        assert_eq!((dry.scanned, dry.rekeyed, dry.merged), (2, 1, 1));
+
+        let report = backfill_item_ids(&pool, &source, IdentityStrategy::Guid, false)
+            .await
+            .unwrap();
+        assert_eq!((report.scanned, report.rekeyed, report.merged), (2, 1, 1));
+
+        // Running again is a no-op, and new polls match the surviving row
+        let again = backfill_item_ids(&pool, &source, IdentityStrategy::Guid, false)
+            .await
+            .unwrap();
+        assert_eq!((again.scanned, again.rekeyed, again.merged), (1, 0, 0));
+
+        let mut polled = vec![corrected];
+        IdentityStrategy::Guid.assign(&mut polled);
+
+        // The surviving row took the correction and its metadata; both
+        // versions of the typo are kept as revisions
+        let stored = get_rss_item(&pool, polled[0].id)
+            .await
+            .unwrap()
+            .expect("merged row");
+        assert_eq!(stored.title, "Fed holds rates");
+        assert_eq!(
+            stored.published_at.timestamp_micros(),
+            polled[0].published_at.timestamp_micros()
+        );
+        assert_eq!(stored.revision_count, 2);
+        assert_eq!(stored.authors, vec!["Markets Desk"]);
+
+        let revisions = item_revisions(&pool, polled[0].id).await.unwrap();
+        let history: Vec<(i32, &str, Option<&str>)> = revisions
+            .iter()
+            .map(|r| (r.revision, r.title.as_str(), r.summary.as_deref()))
+            .collect();
+        assert_eq!(
+            history,
+            vec![
+                (0, "Fed hlods rates", None),
+                (1, "Fed hlods rates", Some("Rates unchanged")),
+            ]
+        );
+        assert_eq!(revisions[0].content_hash, original.content_hash());
+        assert!(revisions[0].superseded_at <= revisions[1].recorded_at);
+
+        // ...so the next poll of the correction is not a change
+        assert_eq!(
+            upsert_rss_item(&pool, &polled[0]).await.unwrap(),
+            UpsertOutcome::Unchanged
+        );
+    }
+}
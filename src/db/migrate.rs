@@ -0,0 +1,86 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use sqlx::migrate::{MigrateError, Migrator};
+
+/// The `migrations/` directory, embedded at compile time
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// One embedded migration and whether the database has applied it
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub applied_at: Option<DateTime<Utc>>,
+    /// Applied, but the embedded SQL no longer matches the recorded checksum
+    pub checksum_mismatch: bool,
+}
+
+impl MigrationStatus {
+    pub fn is_pending(&self) -> bool {
+        self.applied_at.is_none()
+    }
+}
+
+/// Apply every pending embedded migration (what `sqlx migrate run` does)
+pub async fn run_migrations(pool: &PgPool) -> Result<(), MigrateError> {
+    MIGRATOR.run(pool).await
+}
+
+/// Embedded migrations joined with the `_sqlx_migrations` bookkeeping table;
+/// a database that was never migrated reports everything as pending
+pub async fn migration_status(pool: &PgPool) -> Result<Vec<MigrationStatus>, sqlx::Error> {
+    let (exists,): (bool,) = sqlx::query_as("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+        .fetch_one(pool)
+        .await?;
+
+    let applied: Vec<(i64, Vec<u8>, DateTime<Utc>)> = if exists {
+        sqlx::query_as(
+            "SELECT version, checksum, installed_on FROM _sqlx_migrations WHERE success ORDER BY version",
+        )
+        .fetch_all(pool)
+        .await?
+    } else {
+        Vec::new()
+    };
+
+    Ok(MIGRATOR
+        .iter()
+        .filter(|m| m.migration_type.is_up_migration())
+        .map(|m| {
+            let record = applied.iter().find(|(version, _, _)| *version == m.version);
+            MigrationStatus {
+                version: m.version,
+                description: m.description.to_string(),
+                applied_at: record.map(|(_, _, installed_on)| *installed_on),
+                checksum_mismatch: record
+                    .is_some_and(|(_, checksum, _)| checksum.as_slice() != &*m.checksum),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::create_pg_pool;
+    use dotenvy::dotenv;
+
+    #[test]
+    fn test_migrations_are_embedded_in_order() {
+        let versions: Vec<i64> = MIGRATOR.iter().map(|m| m.version).collect();
+        assert_eq!(versions.first(), Some(&100));
+        assert!(versions.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[tokio::test]
+    async fn test_migration_status_covers_every_migration() {
+        dotenv().ok();
+
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = create_pg_pool(&database_url).await;
+
+        let status = migration_status(&pool).await.unwrap();
+        assert_eq!(status.len(), MIGRATOR.iter().count());
+        assert_eq!(status[0].description, "create warehouse schema");
+    }
+}
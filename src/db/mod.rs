@@ -1,7 +1,19 @@
+pub mod clusters;
+pub mod feeds;
+pub mod fetch_runs;
+pub mod http_cache;
+pub mod identity;
+pub mod migrate;
+pub mod payloads;
+pub mod schedule;
 pub mod stats;
 
-use crate::rss::RssItem;
-use sqlx::{PgPool, postgres::PgPoolOptions};
+use crate::rss::identity::IdentityStrategy;
+use crate::rss::{RssCategory, RssItem};
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction, postgres::PgPoolOptions};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
 
 pub async fn create_pg_pool(database_url: &str) -> PgPool {
     PgPoolOptions::new()
@@ -11,32 +23,665 @@ pub async fn create_pg_pool(database_url: &str) -> PgPool {
         .expect("Failed to create PostgreSQL pool")
 }
 
+/// What `insert_rss_items` did with an item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpsertOutcome {
+    Inserted,
+    /// Same ID (or canonical link) and same content hash as the stored row,
+    /// or a repeat of an earlier item of the same batch
+    Unchanged,
+    /// Content changed; the previous version was kept as a revision
+    Updated {
+        revision_count: i32,
+    },
+    /// Not written: another item of the source, stored or earlier in the
+    /// batch, has the same canonical link under a different GUID
+    Conflict {
+        stored_id: Uuid,
+    },
+}
+
 pub async fn insert_rss_item(pool: &PgPool, item: &RssItem) -> Result<bool, sqlx::Error> {
-    // Checks if the row already exists (id, my deterministic hash UUID)
-    // Returns true if inserted, false if skipped
-    // Schema: warehouse, Table: rss_items
-    let result = sqlx::query!(
+    // Returns true if inserted, false if the id (my deterministic hash UUID)
+    // already existed; content changes are still recorded as revisions
+    Ok(upsert_rss_item(pool, item).await? == UpsertOutcome::Inserted)
+}
+
+/// Insert an item, or record a new revision when an item with the same ID
+/// comes back with a different title, link or summary
+pub async fn upsert_rss_item(pool: &PgPool, item: &RssItem) -> Result<UpsertOutcome, sqlx::Error> {
+    let outcomes = insert_rss_items(pool, std::slice::from_ref(item)).await?;
+    Ok(outcomes[0])
+}
+
+/// Write a whole feed's items in one transaction with a fixed number of
+/// statements (UNNEST arrays), returning one outcome per input item.
+///
+/// New IDs are inserted; known IDs with a different content hash get their
+/// previous version copied to `rss_item_revisions` and are updated in place.
+/// An item with a new ID whose canonical link is already stored for its
+/// source is treated as that stored row if it has no GUID, is keyed by link
+/// or carries the row's GUID; otherwise it is reported as a conflict.
+pub async fn insert_rss_items(
+    pool: &PgPool,
+    items: &[RssItem],
+) -> Result<Vec<UpsertOutcome>, sqlx::Error> {
+    // Schema: warehouse, Tables: rss_items, rss_item_authors, rss_item_categories, rss_item_revisions
+    let mut outcomes = vec![UpsertOutcome::Unchanged; items.len()];
+    if items.is_empty() {
+        return Ok(outcomes);
+    }
+
+    let mut batch = dedupe_batch(items, &mut outcomes);
+
+    let mut tx = pool.begin().await?;
+    lock_sources(&mut tx, &batch).await?;
+    remap_known_links(&mut tx, &mut batch, &mut outcomes).await?;
+
+    // Rows are written and locked in ID order, so feeds sharing a source
+    // and carrying the same items in another order can't deadlock
+    batch.sort_by_key(|entry| entry.id);
+
+    let inserted = insert_new_items(&mut tx, &batch).await?;
+    let stored = lock_stored_items(&mut tx, &batch, &inserted).await?;
+    refresh_derived_columns(&mut tx, &batch).await?;
+
+    let changed: Vec<&BatchItem> = batch
+        .iter()
+        .filter(|entry| {
+            stored
+                .get(&entry.id)
+                .is_some_and(|row| row.is_changed(entry))
+        })
+        .collect();
+    let revision_counts = write_revisions(&mut tx, &changed, &stored).await?;
+
+    let written: Vec<&BatchItem> = batch
+        .iter()
+        .filter(|entry| inserted.contains(&entry.id) || revision_counts.contains_key(&entry.id))
+        .collect();
+    insert_item_metadata(&mut tx, &written).await?;
+
+    tx.commit().await?;
+
+    for entry in &batch {
+        if inserted.contains(&entry.id) {
+            outcomes[entry.index] = UpsertOutcome::Inserted;
+        } else if let Some(revision_count) = revision_counts.get(&entry.id) {
+            outcomes[entry.index] = UpsertOutcome::Updated {
+                revision_count: *revision_count,
+            };
+        }
+    }
+    Ok(outcomes)
+}
+
+/// An item of the batch under the ID it is stored with
+struct BatchItem<'a> {
+    /// Position in the input slice
+    index: usize,
+    item: &'a RssItem,
+    id: Uuid,
+    hash: String,
+    /// `content_hash` over the raw link, as stored before canonical links
+    raw_hash: String,
+}
+
+/// The locked state of a known item
+struct StoredItem {
+    content_hash: Option<String>,
+    cluster_id: Option<Uuid>,
+}
+
+impl StoredItem {
+    /// A hash still taken over the raw link is the same content
+    fn is_changed(&self, entry: &BatchItem) -> bool {
+        let stored_hash = self.content_hash.as_deref();
+        stored_hash != Some(entry.hash.as_str()) && stored_hash != Some(entry.raw_hash.as_str())
+    }
+}
+
+/// Whether `item` may stand for the row (or earlier batch item) that has
+/// its canonical link: it has no GUID, it is keyed by link, or the GUIDs
+/// agree. Publishers reuse one landing page for distinct live-blog or
+/// digest entries, which must not overwrite each other.
+fn same_article(item: &RssItem, other_guid: Option<&str>) -> bool {
+    match non_empty_guid(item.guid.as_deref()) {
+        None => true,
+        Some(guid) => {
+            item.id == IdentityStrategy::Link.item_id(item)
+                || non_empty_guid(other_guid) == Some(guid)
+        }
+    }
+}
+
+fn non_empty_guid(guid: Option<&str>) -> Option<&str> {
+    guid.map(str::trim).filter(|guid| !guid.is_empty())
+}
+
+/// The items to write, first occurrences only: a payload may repeat an
+/// item, also under another URL variant. A link repeated under another
+/// GUID is a conflict with its first occurrence.
+fn dedupe_batch<'a>(items: &'a [RssItem], outcomes: &mut [UpsertOutcome]) -> Vec<BatchItem<'a>> {
+    let mut seen_ids = HashSet::new();
+    let mut seen_links: HashMap<&str, &RssItem> = HashMap::new();
+    let mut batch = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        if !seen_ids.insert(item.id) {
+            continue;
+        }
+        if let Some(link) = item.canonical_link.as_deref() {
+            if let Some(first) = seen_links.get(link) {
+                if !same_article(item, first.guid.as_deref()) {
+                    outcomes[index] = UpsertOutcome::Conflict {
+                        stored_id: first.id,
+                    };
+                }
+                continue;
+            }
+            seen_links.insert(link, item);
+        }
+        batch.push(BatchItem {
+            index,
+            item,
+            id: item.id,
+            hash: item.content_hash(),
+            raw_hash: item.raw_content_hash(),
+        });
+    }
+    batch
+}
+
+/// Serialize writers of the same source, so no other transaction can store
+/// a canonical link between `remap_known_links` looking it up and the insert
+async fn lock_sources(
+    tx: &mut Transaction<'_, Postgres>,
+    batch: &[BatchItem<'_>],
+) -> Result<(), sqlx::Error> {
+    let mut sources: Vec<&str> = batch
+        .iter()
+        .map(|entry| entry.item.source.as_str())
+        .collect();
+    sources.sort_unstable();
+    sources.dedup();
+    for source in sources {
+        sqlx::query!(
+            "SELECT pg_advisory_xact_lock(hashtextextended($1, 0))",
+            source
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Point new IDs at the stored row of their source with the same canonical
+/// link. With the `link` strategy the ID already follows the canonical
+/// link; this catches legacy and `guid` IDs, and rows keyed before that.
+async fn remap_known_links(
+    tx: &mut Transaction<'_, Postgres>,
+    batch: &mut Vec<BatchItem<'_>>,
+    outcomes: &mut [UpsertOutcome],
+) -> Result<(), sqlx::Error> {
+    let rows = ItemColumns::new(batch.iter());
+    let known_links: HashMap<Uuid, (Uuid, Option<String>)> = sqlx::query!(
         r#"
-        INSERT INTO warehouse.rss_items (id, source, title, link, summary, published_at)
-        VALUES ($1, $2, $3, $4, $5, $6)
+        SELECT u.id AS "id!", i.id AS stored_id, i.guid
+        FROM UNNEST($1::UUID[], $2::TEXT[], $3::TEXT[]) AS u(id, source, canonical_link)
+        JOIN warehouse.rss_items AS i
+          ON i.source = u.source AND i.canonical_link = u.canonical_link
+        WHERE i.id <> u.id
+          AND NOT EXISTS (SELECT 1 FROM warehouse.rss_items AS s WHERE s.id = u.id)
+        "#,
+        &rows.ids,
+        &rows.sources,
+        &rows.canonical_links as &[Option<String>],
+    )
+    .fetch_all(&mut **tx)
+    .await?
+    .into_iter()
+    .map(|row| (row.id, (row.stored_id, row.guid)))
+    .collect();
+    if known_links.is_empty() {
+        return Ok(());
+    }
+
+    let mut seen = HashSet::new();
+    batch.retain_mut(|entry| {
+        if let Some((stored_id, stored_guid)) = known_links.get(&entry.id) {
+            if !same_article(entry.item, stored_guid.as_deref()) {
+                outcomes[entry.index] = UpsertOutcome::Conflict {
+                    stored_id: *stored_id,
+                };
+                return false;
+            }
+            entry.id = *stored_id;
+        }
+        seen.insert(entry.id)
+    });
+    Ok(())
+}
+
+/// Insert the items with new IDs, returning those IDs. Under the source
+/// lock a canonical link can't have been stored since the remap, so a
+/// violation of its unique index is an error rather than a skipped item.
+async fn insert_new_items(
+    tx: &mut Transaction<'_, Postgres>,
+    batch: &[BatchItem<'_>],
+) -> Result<HashSet<Uuid>, sqlx::Error> {
+    let rows = ItemColumns::new(batch);
+    let inserted = sqlx::query_scalar!(
+        r#"
+        INSERT INTO warehouse.rss_items
+            (id, source, title, link, canonical_link, summary, summary_text, summary_html,
+             published_at, published_at_fallback, guid, content_hash, feed)
+        SELECT * FROM UNNEST(
+            $1::UUID[], $2::TEXT[], $3::TEXT[], $4::TEXT[], $5::TEXT[], $6::TEXT[], $7::TEXT[],
+            $8::TEXT[], $9::TIMESTAMPTZ[], $10::BOOL[], $11::TEXT[], $12::TEXT[], $13::TEXT[]
+        )
         ON CONFLICT (id) DO NOTHING
+        RETURNING id
+        "#,
+        &rows.ids,
+        &rows.sources,
+        &rows.titles,
+        &rows.links,
+        &rows.canonical_links as &[Option<String>],
+        &rows.summaries as &[Option<String>],
+        &rows.summary_texts as &[Option<String>],
+        &rows.summary_htmls as &[Option<String>],
+        &rows.published_at,
+        &rows.fallbacks,
+        &rows.guids as &[Option<String>],
+        &rows.hashes,
+        &rows.feeds as &[Option<String>],
+    )
+    .fetch_all(&mut **tx)
+    .await?
+    .into_iter()
+    .collect();
+    Ok(inserted)
+}
+
+/// Lock the batch's known rows in ID order and read their current state
+async fn lock_stored_items(
+    tx: &mut Transaction<'_, Postgres>,
+    batch: &[BatchItem<'_>],
+    inserted: &HashSet<Uuid>,
+) -> Result<HashMap<Uuid, StoredItem>, sqlx::Error> {
+    let existing: Vec<Uuid> = batch
+        .iter()
+        .map(|entry| entry.id)
+        .filter(|id| !inserted.contains(id))
+        .collect();
+    let stored = sqlx::query!(
+        r#"
+        SELECT id, content_hash, cluster_id
+        FROM warehouse.rss_items
+        WHERE id = ANY($1)
+        ORDER BY id
+        FOR UPDATE
+        "#,
+        &existing
+    )
+    .fetch_all(&mut **tx)
+    .await?
+    .into_iter()
+    .map(|row| {
+        let stored = StoredItem {
+            content_hash: row.content_hash,
+            cluster_id: row.cluster_id,
+        };
+        (row.id, stored)
+    })
+    .collect();
+    Ok(stored)
+}
+
+/// Derived columns follow the current normalizers, so known items pick up
+/// their changes without a new revision. A canonical link another row of
+/// the source already has is left alone. A hash still taken over the raw
+/// link is the same content and is rewritten in place.
+async fn refresh_derived_columns(
+    tx: &mut Transaction<'_, Postgres>,
+    batch: &[BatchItem<'_>],
+) -> Result<(), sqlx::Error> {
+    let rows = ItemColumns::new(batch);
+    sqlx::query!(
+        r#"
+        UPDATE warehouse.rss_items AS i
+        SET summary_text = u.summary_text,
+            summary_html = u.summary_html,
+            canonical_link = CASE
+                WHEN u.canonical_link IS NULL OR EXISTS (
+                    SELECT 1 FROM warehouse.rss_items AS o
+                    WHERE o.source = i.source AND o.canonical_link = u.canonical_link
+                      AND o.id <> i.id
+                ) THEN i.canonical_link
+                ELSE u.canonical_link
+            END,
+            content_hash = CASE
+                WHEN i.content_hash = u.raw_hash THEN u.content_hash
+                ELSE i.content_hash
+            END
+        FROM UNNEST($1::UUID[], $2::TEXT[], $3::TEXT[], $4::TEXT[], $5::TEXT[], $6::TEXT[])
+            AS u(id, summary_text, summary_html, canonical_link, content_hash, raw_hash)
+        WHERE i.id = u.id
+          AND (i.summary_text IS DISTINCT FROM u.summary_text
+               OR i.summary_html IS DISTINCT FROM u.summary_html
+               OR (u.canonical_link IS NOT NULL
+                   AND i.canonical_link IS DISTINCT FROM u.canonical_link)
+               OR (i.content_hash = u.raw_hash AND u.raw_hash <> u.content_hash))
+        "#,
+        &rows.ids,
+        &rows.summary_texts as &[Option<String>],
+        &rows.summary_htmls as &[Option<String>],
+        &rows.canonical_links as &[Option<String>],
+        &rows.hashes,
+        &rows.raw_hashes,
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Copy the stored version of each changed item to `rss_item_revisions`
+/// and update the row, returning the new revision counts. Authors and
+/// categories are cleared for `insert_item_metadata` to write again.
+async fn write_revisions(
+    tx: &mut Transaction<'_, Postgres>,
+    changed: &[&BatchItem<'_>],
+    stored: &HashMap<Uuid, StoredItem>,
+) -> Result<HashMap<Uuid, i32>, sqlx::Error> {
+    if changed.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let rows = ItemColumns::new(changed.iter().copied());
+    sqlx::query!(
+        r#"
+        INSERT INTO warehouse.rss_item_revisions
+            (item_id, revision, title, link, summary, published_at, content_hash, recorded_at)
+        SELECT id, revision_count, title, link, summary, published_at,
+               COALESCE(content_hash, ''), COALESCE(last_updated_at, created_at)
+        FROM warehouse.rss_items
+        WHERE id = ANY($1)
         "#,
-        item.id, // UUID generated by source + title + published_at
-        item.source,
-        item.title,
-        item.link,
-        item.summary,
-        item.published_at,
+        &rows.ids
     )
-    .execute(pool)
+    .execute(&mut **tx)
     .await?;
 
-    Ok(result.rows_affected() > 0)
+    // A first-seen fallback date never replaces a stored one. The new text
+    // needs a new story signature, so the item leaves its cluster and is
+    // signed again by the next clustering pass.
+    let revision_counts = sqlx::query!(
+        r#"
+        UPDATE warehouse.rss_items AS i
+        SET title = u.title,
+            link = u.link,
+            summary = u.summary,
+            published_at = CASE WHEN u.fallback THEN i.published_at ELSE u.published_at END,
+            guid = COALESCE(u.guid, i.guid),
+            content_hash = u.content_hash,
+            revision_count = i.revision_count + 1,
+            last_updated_at = now(),
+            simhash = NULL,
+            minhash = NULL,
+            cluster_id = NULL
+        FROM UNNEST(
+            $1::UUID[], $2::TEXT[], $3::TEXT[], $4::TEXT[],
+            $5::TIMESTAMPTZ[], $6::BOOL[], $7::TEXT[], $8::TEXT[]
+        ) AS u(id, title, link, summary, published_at, fallback, guid, content_hash)
+        WHERE i.id = u.id
+        RETURNING i.id, i.revision_count
+        "#,
+        &rows.ids,
+        &rows.titles,
+        &rows.links,
+        &rows.summaries as &[Option<String>],
+        &rows.published_at,
+        &rows.fallbacks,
+        &rows.guids as &[Option<String>],
+        &rows.hashes,
+    )
+    .fetch_all(&mut **tx)
+    .await?
+    .into_iter()
+    .map(|row| (row.id, row.revision_count))
+    .collect();
+
+    let mut left_clusters: Vec<Uuid> = changed
+        .iter()
+        .filter_map(|entry| stored.get(&entry.id).and_then(|row| row.cluster_id))
+        .collect();
+    left_clusters.sort();
+    left_clusters.dedup();
+    clusters::refresh_clusters(tx, &left_clusters).await?;
+
+    sqlx::query!(
+        "DELETE FROM warehouse.rss_item_authors WHERE item_id = ANY($1)",
+        &rows.ids
+    )
+    .execute(&mut **tx)
+    .await?;
+    sqlx::query!(
+        "DELETE FROM warehouse.rss_item_categories WHERE item_id = ANY($1)",
+        &rows.ids
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(revision_counts)
+}
+
+/// Column arrays for the UNNEST statements
+struct ItemColumns {
+    ids: Vec<Uuid>,
+    sources: Vec<String>,
+    titles: Vec<String>,
+    links: Vec<String>,
+    canonical_links: Vec<Option<String>>,
+    summaries: Vec<Option<String>>,
+    summary_texts: Vec<Option<String>>,
+    summary_htmls: Vec<Option<String>>,
+    published_at: Vec<DateTime<Utc>>,
+    fallbacks: Vec<bool>,
+    guids: Vec<Option<String>>,
+    hashes: Vec<String>,
+    raw_hashes: Vec<String>,
+    feeds: Vec<Option<String>>,
+}
+
+impl ItemColumns {
+    fn new<'a, 'b: 'a>(entries: impl IntoIterator<Item = &'a BatchItem<'b>>) -> Self {
+        let mut columns = Self {
+            ids: Vec::new(),
+            sources: Vec::new(),
+            titles: Vec::new(),
+            links: Vec::new(),
+            canonical_links: Vec::new(),
+            summaries: Vec::new(),
+            summary_texts: Vec::new(),
+            summary_htmls: Vec::new(),
+            published_at: Vec::new(),
+            fallbacks: Vec::new(),
+            guids: Vec::new(),
+            hashes: Vec::new(),
+            raw_hashes: Vec::new(),
+            feeds: Vec::new(),
+        };
+        for BatchItem {
+            item,
+            id,
+            hash,
+            raw_hash,
+            ..
+        } in entries
+        {
+            columns.ids.push(*id);
+            columns.sources.push(item.source.clone());
+            columns.titles.push(item.title.clone());
+            columns.links.push(item.link.clone());
+            columns.canonical_links.push(item.canonical_link.clone());
+            columns.summaries.push(item.summary.clone());
+            columns.summary_texts.push(item.summary_text.clone());
+            columns.summary_htmls.push(item.summary_html.clone());
+            columns.published_at.push(item.published_at);
+            columns.fallbacks.push(item.published_at_fallback);
+            columns.guids.push(item.guid.clone());
+            columns.hashes.push(hash.clone());
+            columns.raw_hashes.push(raw_hash.clone());
+            columns.feeds.push(item.feed.clone());
+        }
+        columns
+    }
+}
+
+/// Authors and categories of the written items, kept in feed order
+async fn insert_item_metadata(
+    tx: &mut Transaction<'_, Postgres>,
+    entries: &[&BatchItem<'_>],
+) -> Result<(), sqlx::Error> {
+    let (mut author_items, mut author_positions, mut author_names) = (vec![], vec![], vec![]);
+    let (mut category_items, mut category_positions) = (vec![], vec![]);
+    let (mut category_names, mut category_domains) = (vec![], vec![]);
+
+    for BatchItem { item, id, .. } in entries {
+        for (position, name) in item.authors.iter().enumerate() {
+            author_items.push(*id);
+            author_positions.push(position as i32);
+            author_names.push(name.clone());
+        }
+        for (position, category) in item.categories.iter().enumerate() {
+            category_items.push(*id);
+            category_positions.push(position as i32);
+            category_names.push(category.name.clone());
+            category_domains.push(category.domain.clone());
+        }
+    }
+
+    if !author_items.is_empty() {
+        sqlx::query!(
+            r#"
+            INSERT INTO warehouse.rss_item_authors (item_id, position, name)
+            SELECT * FROM UNNEST($1::UUID[], $2::INTEGER[], $3::TEXT[])
+            "#,
+            &author_items,
+            &author_positions,
+            &author_names,
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    if !category_items.is_empty() {
+        sqlx::query!(
+            r#"
+            INSERT INTO warehouse.rss_item_categories (item_id, position, name, domain)
+            SELECT * FROM UNNEST($1::UUID[], $2::INTEGER[], $3::TEXT[], $4::TEXT[])
+            "#,
+            &category_items,
+            &category_positions,
+            &category_names,
+            &category_domains as &[Option<String>],
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// A stored item with its authors, categories and revision count
+pub async fn get_rss_item(pool: &PgPool, id: Uuid) -> Result<Option<RssItem>, sqlx::Error> {
+    let Some(row) = sqlx::query!(
+        r#"
+        SELECT id, source, title, link, canonical_link, summary, summary_text, summary_html,
+               published_at, published_at_fallback, guid, feed, revision_count, last_updated_at
+        FROM warehouse.rss_items
+        WHERE id = $1
+        "#,
+        id
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let authors = sqlx::query_scalar!(
+        "SELECT name FROM warehouse.rss_item_authors WHERE item_id = $1 ORDER BY position",
+        id
+    )
+    .fetch_all(pool)
+    .await?;
+    let categories = sqlx::query!(
+        "SELECT name, domain FROM warehouse.rss_item_categories WHERE item_id = $1 ORDER BY position",
+        id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|r| RssCategory::new(r.name, r.domain))
+    .collect();
+
+    Ok(Some(RssItem {
+        id: row.id,
+        source: row.source,
+        title: row.title,
+        link: row.link,
+        canonical_link: row.canonical_link,
+        summary: row.summary,
+        summary_text: row.summary_text,
+        summary_html: row.summary_html,
+        published_at: row.published_at,
+        published_at_fallback: row.published_at_fallback,
+        guid: row.guid,
+        authors,
+        categories,
+        feed: row.feed,
+        revision_count: row.revision_count,
+        last_updated_at: row.last_updated_at,
+    }))
+}
+
+/// A superseded version of a stored item
+#[derive(Debug, Clone)]
+pub struct ItemRevision {
+    pub revision: i32,
+    pub title: String,
+    pub link: String,
+    pub summary: Option<String>,
+    pub published_at: DateTime<Utc>,
+    pub content_hash: String,
+    /// When this version was first stored
+    pub recorded_at: DateTime<Utc>,
+    /// When a newer version replaced it
+    pub superseded_at: DateTime<Utc>,
+}
+
+pub async fn item_revisions(
+    pool: &PgPool,
+    item_id: Uuid,
+) -> Result<Vec<ItemRevision>, sqlx::Error> {
+    sqlx::query_as!(
+        ItemRevision,
+        r#"
+        SELECT revision, title, link, summary, published_at, content_hash, recorded_at, superseded_at
+        FROM warehouse.rss_item_revisions
+        WHERE item_id = $1
+        ORDER BY revision
+        "#,
+        item_id
+    )
+    .fetch_all(pool)
+    .await
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rss::link::LinkCanonicalizer;
     use dotenvy::dotenv;
 
     #[tokio::test]
@@ -95,4 +740,429 @@ mod tests {
         assert!(insert_rss_item(&pool, &item).await.unwrap()); // true
         assert!(!insert_rss_item(&pool, &item).await.unwrap()); // false; because it already exists
     }
+
+    #[tokio::test]
+    async fn test_insert_rss_item_metadata() {
+        dotenv().ok();
+
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = create_pg_pool(&database_url).await;
+
+        let item = RssItem::new(
+            "test3",
+            "test3",
+            "https://example.com/test3",
+            None,
+            Some(chrono::Utc::now()),
+        )
+        .with_guid(Some("urn:test3".to_string()))
+        .with_authors(vec!["Jane Doe".to_string(), "John Roe".to_string()])
+        .with_categories(vec![
+            RssCategory::new("markets", Some("topic".to_string())),
+            RssCategory::new("BTC", None),
+        ]);
+
+        assert!(insert_rss_item(&pool, &item).await.unwrap());
+
+        let authors: Vec<(i32, String)> = sqlx::query_as(
+            "SELECT position, name FROM warehouse.rss_item_authors WHERE item_id = $1 ORDER BY position",
+        )
+        .bind(item.id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            authors,
+            vec![(0, "Jane Doe".to_string()), (1, "John Roe".to_string())]
+        );
+
+        let categories: Vec<(String, Option<String>)> = sqlx::query_as(
+            "SELECT name, domain FROM warehouse.rss_item_categories WHERE item_id = $1 ORDER BY position",
+        )
+        .bind(item.id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            categories,
+            vec![
+                ("markets".to_string(), Some("topic".to_string())),
+                ("BTC".to_string(), None)
+            ]
+        );
+
+        // A duplicate leaves the stored metadata untouched
+        assert!(!insert_rss_item(&pool, &item).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_upsert_rss_item_records_revisions() {
+        dotenv().ok();
+
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = create_pg_pool(&database_url).await;
+
+        let link = format!("https://example.com/{}", Uuid::new_v4());
+        let published_at = chrono::Utc::now();
+        let original = RssItem::new("test4", "Stocks slip", &link, None, Some(published_at));
+        let mut edited = RssItem::new(
+            "test4",
+            "Stocks slump",
+            &link,
+            Some("Updated with closing prices".to_string()),
+            Some(published_at),
+        )
+        .with_authors(vec!["Markets Desk".to_string()]);
+        edited.id = original.id; // as with a guid / link identity strategy
+
+        assert_eq!(
+            upsert_rss_item(&pool, &original).await.unwrap(),
+            UpsertOutcome::Inserted
+        );
+        assert_eq!(
+            upsert_rss_item(&pool, &original).await.unwrap(),
+            UpsertOutcome::Unchanged
+        );
+        assert_eq!(
+            upsert_rss_item(&pool, &edited).await.unwrap(),
+            UpsertOutcome::Updated { revision_count: 1 }
+        );
+        assert_eq!(
+            upsert_rss_item(&pool, &edited).await.unwrap(),
+            UpsertOutcome::Unchanged
+        );
+
+        let revisions = item_revisions(&pool, original.id).await.unwrap();
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].revision, 0);
+        assert_eq!(revisions[0].title, "Stocks slip");
+        assert_eq!(revisions[0].content_hash, original.content_hash());
+
+        let stored = get_rss_item(&pool, original.id)
+            .await
+            .unwrap()
+            .expect("stored item");
+        assert_eq!(stored.title, "Stocks slump");
+        assert_eq!(stored.revision_count, 1);
+        assert!(stored.last_updated_at.is_some());
+        assert_eq!(stored.authors, vec!["Markets Desk"]);
+        assert!(get_rss_item(&pool, Uuid::new_v4()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_content_hash_matches_sql_backfill() {
+        dotenv().ok();
+
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = create_pg_pool(&database_url).await;
+
+        let item = RssItem::new(
+            "test5",
+            "Caf\u{e9} \u{2014} r\u{e9}sum\u{e9}",
+            "https://example.com/cafe",
+            None,
+            Some(chrono::Utc::now()),
+        );
+        let (hash,): (String,) = sqlx::query_as(
+            "SELECT encode(sha256(convert_to($1 || chr(31) || $2 || chr(31) || COALESCE($3, ''), 'UTF8')), 'hex')",
+        )
+        .bind(&item.title)
+        .bind(&item.link)
+        .bind(&item.summary)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(hash, item.raw_content_hash());
+    }
+
+    #[tokio::test]
+    async fn test_insert_rss_items_batch() {
+        dotenv().ok();
+
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = create_pg_pool(&database_url).await;
+
+        let source = format!("batch_{}", Uuid::new_v4());
+        let published_at = chrono::Utc::now();
+        let item = |title: &str, summary: Option<&str>| {
+            RssItem::new(
+                &source,
+                title,
+                format!("https://example.com/{title}"),
+                summary.map(str::to_string),
+                Some(published_at),
+            )
+            .with_categories(vec![RssCategory::new(title, None)])
+        };
+
+        let first = vec![item("a", None), item("b", None)];
+        assert_eq!(
+            insert_rss_items(&pool, &first).await.unwrap(),
+            vec![UpsertOutcome::Inserted, UpsertOutcome::Inserted]
+        );
+
+        let second = vec![
+            item("a", None),
+            item("b", Some("now with a summary")),
+            item("c", None),
+            item("c", None),
+        ];
+        assert_eq!(
+            insert_rss_items(&pool, &second).await.unwrap(),
+            vec![
+                UpsertOutcome::Unchanged,
+                UpsertOutcome::Updated { revision_count: 1 },
+                UpsertOutcome::Inserted,
+                UpsertOutcome::Unchanged,
+            ]
+        );
+        assert!(insert_rss_items(&pool, &[]).await.unwrap().is_empty());
+
+        let (items, categories): (i64, i64) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*),
+                   (SELECT COUNT(*) FROM warehouse.rss_item_categories c
+                    JOIN warehouse.rss_items i ON i.id = c.item_id WHERE i.source = $1)
+            FROM warehouse.rss_items WHERE source = $1
+            "#,
+        )
+        .bind(&source)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!((items, categories), (3, 3));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_batches_in_different_orders() {
+        dotenv().ok();
+
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = create_pg_pool(&database_url).await;
+
+        // Two feeds of one source carrying the same items in opposite orders
+        let source = format!("order_{}", Uuid::new_v4());
+        let published_at = chrono::Utc::now();
+        let item = |n: usize, round: usize| {
+            let mut item = RssItem::new(
+                &source,
+                format!("story {n}"),
+                format!("https://example.com/{n}"),
+                Some(format!("version {round}")),
+                Some(published_at),
+            );
+            item.summary_text = Some(format!("version {round}"));
+            item
+        };
+
+        for round in 0..10 {
+            let forward: Vec<RssItem> = (0..500).map(|n| item(n, round)).collect();
+            let reversed: Vec<RssItem> = (0..500).rev().map(|n| item(n, round)).collect();
+            let (a, b) = tokio::join!(
+                insert_rss_items(&pool, &forward),
+                insert_rss_items(&pool, &reversed)
+            );
+            a.expect("first feed");
+            b.expect("second feed");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_normalized_summaries_are_stored() {
+        dotenv().ok();
+
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = create_pg_pool(&database_url).await;
+
+        let source = format!("summary_{}", Uuid::new_v4());
+        let raw =
+            r#"<p>Rates <b>held</b>&nbsp;steady</p><img src="https://e.com/p.gif" width="1">"#;
+        let mut item = RssItem::new(
+            &source,
+            "Fed holds",
+            "https://e.com/fed",
+            Some(raw.to_string()),
+            Some(chrono::Utc::now()),
+        );
+
+        // Stored before normalization existed: the next poll fills it in
+        assert!(insert_rss_item(&pool, &item).await.unwrap());
+        item.normalize_summary(100);
+        assert_eq!(
+            upsert_rss_item(&pool, &item).await.unwrap(),
+            UpsertOutcome::Unchanged
+        );
+
+        let (text, html, summary): (Option<String>, Option<String>, Option<String>) =
+            sqlx::query_as(
+                "SELECT summary_text, summary_html, summary FROM warehouse.rss_items WHERE id = $1",
+            )
+            .bind(item.id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(text.as_deref(), Some("Rates held steady"));
+        assert_eq!(
+            html.as_deref(),
+            Some("<p>Rates <b>held</b>&nbsp;steady</p>")
+        );
+        assert_eq!(summary.as_deref(), Some(raw));
+    }
+
+    #[tokio::test]
+    async fn test_raw_link_hash_is_not_a_revision() {
+        dotenv().ok();
+
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = create_pg_pool(&database_url).await;
+
+        // Stored before canonical links: the hash covers the tracked link
+        let mut item = RssItem::new(
+            format!("rehash_{}", Uuid::new_v4()),
+            "Fed holds",
+            "https://e.com/fed?srnd=markets",
+            None,
+            Some(chrono::Utc::now()),
+        );
+        assert!(insert_rss_item(&pool, &item).await.unwrap());
+
+        LinkCanonicalizer::default().apply(std::slice::from_mut(&mut item), None);
+        assert_ne!(item.content_hash(), item.raw_content_hash());
+        assert_eq!(
+            upsert_rss_item(&pool, &item).await.unwrap(),
+            UpsertOutcome::Unchanged
+        );
+
+        let stored = get_rss_item(&pool, item.id).await.unwrap().unwrap();
+        assert_eq!(stored.revision_count, 0);
+        assert_eq!(stored.canonical_link.as_deref(), Some("https://e.com/fed"));
+        let (hash,): (Option<String>,) =
+            sqlx::query_as("SELECT content_hash FROM warehouse.rss_items WHERE id = $1")
+                .bind(item.id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(hash, Some(item.content_hash()));
+    }
+
+    #[tokio::test]
+    async fn test_canonical_link_dedupes_url_variants() {
+        dotenv().ok();
+
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = create_pg_pool(&database_url).await;
+
+        let source = format!("canonical_{}", Uuid::new_v4());
+        let links = LinkCanonicalizer::default();
+        let item = |title: &str, link: &str| {
+            let mut items = vec![RssItem::new(
+                &source,
+                title,
+                link,
+                None,
+                Some(chrono::Utc::now()),
+            )];
+            links.apply(&mut items, None);
+            items.pop().unwrap()
+        };
+
+        // The same article twice in one payload, under two tracking variants
+        let first = item("Fed holds", "https://e.com/fed?utm_source=rss");
+        let repeat = item("Fed holds (wire)", "https://E.com/fed?srnd=markets");
+        assert_eq!(
+            insert_rss_items(&pool, &[first, repeat]).await.unwrap(),
+            vec![UpsertOutcome::Inserted, UpsertOutcome::Unchanged]
+        );
+
+        // A corrected headline gets a new legacy ID, but the link finds the row
+        let corrected = item("Fed holds rates", "https://e.com/fed#comments");
+        assert_eq!(
+            upsert_rss_item(&pool, &corrected).await.unwrap(),
+            UpsertOutcome::Updated { revision_count: 1 }
+        );
+
+        let rows: Vec<(String, String, Option<String>)> = sqlx::query_as(
+            "SELECT title, link, canonical_link FROM warehouse.rss_items WHERE source = $1",
+        )
+        .bind(&source)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            rows,
+            vec![(
+                "Fed holds rates".to_string(),
+                "https://e.com/fed#comments".to_string(),
+                Some("https://e.com/fed".to_string())
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shared_link_keeps_distinct_guids_apart() {
+        dotenv().ok();
+
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = create_pg_pool(&database_url).await;
+
+        let source = format!("shared_link_{}", Uuid::new_v4());
+        let links = LinkCanonicalizer::default();
+        let item = |title: &str, guid: &str| {
+            let mut items = vec![
+                RssItem::new(
+                    &source,
+                    title,
+                    "https://e.com/live/markets?utm_source=rss",
+                    None,
+                    Some(chrono::Utc::now()),
+                )
+                .with_guid(Some(guid.to_string())),
+            ];
+            links.apply(&mut items, None);
+            items.pop().unwrap()
+        };
+
+        // Two live-blog entries on one landing page, in one payload
+        let open = item("Stocks open higher", "live-1");
+        let close = item("Stocks close flat", "live-2");
+        let stored_id = open.id;
+        assert_eq!(
+            insert_rss_items(&pool, &[open, close]).await.unwrap(),
+            vec![
+                UpsertOutcome::Inserted,
+                UpsertOutcome::Conflict { stored_id }
+            ]
+        );
+
+        // A later entry of the page neither overwrites the stored one...
+        let midday = item("Stocks hold gains at midday", "live-3");
+        assert_eq!(
+            upsert_rss_item(&pool, &midday).await.unwrap(),
+            UpsertOutcome::Conflict { stored_id }
+        );
+
+        // ...while a corrected headline under the same GUID is a revision
+        let corrected = item("Stocks open sharply higher", "live-1");
+        assert_ne!(corrected.id, stored_id);
+        assert_eq!(
+            upsert_rss_item(&pool, &corrected).await.unwrap(),
+            UpsertOutcome::Updated { revision_count: 1 }
+        );
+
+        let titles: Vec<String> =
+            sqlx::query_scalar("SELECT title FROM warehouse.rss_items WHERE source = $1")
+                .bind(&source)
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(titles, vec!["Stocks open sharply higher".to_string()]);
+
+        // Keyed by link, the entries are one item by design
+        let mut by_link = item("Stocks hold gains at midday", "live-3");
+        by_link.id = IdentityStrategy::Link.item_id(&by_link);
+        assert_eq!(
+            upsert_rss_item(&pool, &by_link).await.unwrap(),
+            UpsertOutcome::Updated { revision_count: 2 }
+        );
+    }
 }
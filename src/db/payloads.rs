@@ -0,0 +1,99 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+/// A `warehouse.feed_payloads` row: one archived response of a feed
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchivedPayload {
+    pub id: i64,
+    pub feed: String,
+    pub fetched_at: DateTime<Utc>,
+    pub sha256: String,
+    /// `postgres` or `disk`
+    pub storage: String,
+    pub size_bytes: i64,
+    /// Encoding the body was decoded from; `None` for payloads archived
+    /// before it was recorded, which were stored as UTF-8
+    pub encoding: Option<String>,
+}
+
+/// Returns false when the same content is already stored
+pub async fn insert_raw_payload(
+    pool: &PgPool,
+    sha256: &str,
+    size_bytes: i64,
+    gzip: &[u8],
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO warehouse.raw_payloads (sha256, size_bytes, gzip)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (sha256) DO NOTHING
+        "#,
+        sha256,
+        size_bytes,
+        gzip
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// The gzip-compressed body stored under `sha256`
+pub async fn get_raw_payload(pool: &PgPool, sha256: &str) -> Result<Option<Vec<u8>>, sqlx::Error> {
+    sqlx::query_scalar!(
+        "SELECT gzip FROM warehouse.raw_payloads WHERE sha256 = $1",
+        sha256
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn record_feed_payload(
+    pool: &PgPool,
+    feed: &str,
+    fetched_at: DateTime<Utc>,
+    sha256: &str,
+    storage: &str,
+    size_bytes: i64,
+    encoding: &str,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        INSERT INTO warehouse.feed_payloads (feed, fetched_at, sha256, storage, size_bytes, encoding)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id
+        "#,
+        feed,
+        fetched_at,
+        sha256,
+        storage,
+        size_bytes,
+        encoding
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Payloads of `feed` fetched in `[since, until)`, oldest first
+pub async fn list_feed_payloads(
+    pool: &PgPool,
+    feed: &str,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Result<Vec<ArchivedPayload>, sqlx::Error> {
+    sqlx::query_as!(
+        ArchivedPayload,
+        r#"
+        SELECT id, feed, fetched_at, sha256, storage, size_bytes, encoding
+        FROM warehouse.feed_payloads
+        WHERE feed = $1 AND fetched_at >= $2 AND fetched_at < $3
+        ORDER BY fetched_at, id
+        "#,
+        feed,
+        since,
+        until
+    )
+    .fetch_all(pool)
+    .await
+}
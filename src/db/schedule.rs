@@ -0,0 +1,117 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+/// A `warehouse.feed_schedule` row
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedScheduleRecord {
+    pub feed: String,
+    pub interval_secs: i32,
+    pub adaptive: bool,
+    pub cadence_secs: Option<i32>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub next_run_at: DateTime<Utc>,
+}
+
+pub async fn list_feed_schedules(pool: &PgPool) -> Result<Vec<FeedScheduleRecord>, sqlx::Error> {
+    sqlx::query_as!(
+        FeedScheduleRecord,
+        r#"
+        SELECT feed, interval_secs, adaptive, cadence_secs, last_run_at, next_run_at
+        FROM warehouse.feed_schedule
+        ORDER BY next_run_at, feed
+        "#
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn save_feed_schedule(
+    pool: &PgPool,
+    record: &FeedScheduleRecord,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO warehouse.feed_schedule
+            (feed, interval_secs, adaptive, cadence_secs, last_run_at, next_run_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (feed) DO UPDATE
+        SET interval_secs = EXCLUDED.interval_secs,
+            adaptive = EXCLUDED.adaptive,
+            cadence_secs = EXCLUDED.cadence_secs,
+            last_run_at = EXCLUDED.last_run_at,
+            next_run_at = EXCLUDED.next_run_at,
+            updated_at = now()
+        "#,
+        record.feed,
+        record.interval_secs,
+        record.adaptive,
+        record.cadence_secs,
+        record.last_run_at,
+        record.next_run_at,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Drop rows of feeds that are no longer configured or registered
+pub async fn prune_feed_schedules(pool: &PgPool, feeds: &[String]) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        "DELETE FROM warehouse.feed_schedule WHERE feed <> ALL($1)",
+        feeds
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::create_pg_pool;
+    use chrono::{Duration, SubsecRound};
+    use dotenvy::dotenv;
+
+    #[tokio::test]
+    async fn test_save_feed_schedule_upserts() {
+        dotenv().ok();
+
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = create_pg_pool(&database_url).await;
+
+        let now = Utc::now().trunc_subsecs(6);
+        let mut record = FeedScheduleRecord {
+            feed: format!("schedule_{}", uuid::Uuid::new_v4()),
+            interval_secs: 60,
+            adaptive: true,
+            cadence_secs: None,
+            last_run_at: None,
+            next_run_at: now,
+        };
+        save_feed_schedule(&pool, &record).await.unwrap();
+
+        record.interval_secs = 300;
+        record.cadence_secs = Some(600);
+        record.last_run_at = Some(now);
+        record.next_run_at = now + Duration::seconds(300);
+        save_feed_schedule(&pool, &record).await.unwrap();
+
+        let stored = list_feed_schedules(&pool)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|r| r.feed == record.feed)
+            .unwrap();
+        assert_eq!(stored, record);
+
+        sqlx::query!(
+            "DELETE FROM warehouse.feed_schedule WHERE feed = $1",
+            record.feed
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+}
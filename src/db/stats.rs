@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 
 pub async fn count_total_rss_items(pool: &PgPool) -> Result<i64, sqlx::Error> {
@@ -38,3 +39,35 @@ pub async fn count_source_rss_items(pool: &PgPool, source: &str) -> Result<i64,
 
     Ok(count.unwrap_or(0))
 }
+
+#[derive(Debug, Clone)]
+pub struct FeedCacheStats {
+    pub feed: String,
+    pub has_etag: bool,
+    pub has_last_modified: bool,
+    pub not_modified_count: i64,
+    pub last_not_modified_at: Option<DateTime<Utc>>,
+}
+
+pub async fn feed_cache_stats(pool: &PgPool) -> Result<Vec<FeedCacheStats>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT feed, etag, last_modified, not_modified_count, last_not_modified_at
+        FROM warehouse.feed_http_cache
+        ORDER BY feed
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| FeedCacheStats {
+            feed: r.feed,
+            has_etag: r.etag.is_some(),
+            has_last_modified: r.last_modified.is_some(),
+            not_modified_count: r.not_modified_count,
+            last_not_modified_at: r.last_not_modified_at,
+        })
+        .collect())
+}
@@ -5,21 +5,98 @@ pub enum RssIngestionError {
     #[error("Network request failed: {0}")]
     Network(#[from] reqwest::Error),
 
+    #[error("HTTP request failed with status {0}")]
+    Http(u16),
+
     #[error("RSS parse error: {0}")]
     Parse(#[from] RssParseError),
 
     #[error("Database error: {0}")]
     Database(#[from] sqlx::Error),
 
+    #[error("Payload archive error: {0}")]
+    Archive(#[from] ArchiveError),
+
     #[error("Unexpected error: {0}")]
     Other(String),
 }
 
+impl RssIngestionError {
+    /// Timeouts, connection failures, 5xx and 429 responses are worth retrying
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Network(e) => e.is_timeout() || e.is_connect() || e.is_request() || e.is_body(),
+            Self::Http(status) => *status >= 500 || *status == 429,
+            _ => false,
+        }
+    }
+
+    /// Errors caused by the feed itself (as opposed to our database)
+    pub fn is_feed_failure(&self) -> bool {
+        matches!(self, Self::Network(_) | Self::Http(_) | Self::Parse(_))
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ArchiveError {
+    #[error("Failed to access {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Payload {0} is not in the archive")]
+    Missing(String),
+
+    #[error("Payload {sha256} is corrupt: {reason}")]
+    Corrupt { sha256: String, reason: String },
+
+    #[error("Payload is archived on disk but `ingest.archive.dir` is not set")]
+    NoDirectory,
+}
+
 #[derive(Error, Debug)]
 pub enum RssParseError {
     #[error("XML parsing error: {0}")]
     Xml(String),
 
+    #[error("JSON parsing error: {0}")]
+    Json(String),
+
+    #[error("Unrecognized feed format")]
+    UnknownFormat,
+
     #[error("Date parsing error: {0}")]
     InvalidDate(String),
+
+    #[error("{count} parse warning(s) in strict mode, first: {first}")]
+    Strict { count: usize, first: String },
+}
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Failed to read config file {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("Failed to parse config file: {0}")]
+    Toml(#[from] toml::de::Error),
+
+    #[error("Config file declares no feeds")]
+    Empty,
+
+    #[error("Invalid setting `{key}`: {reason}")]
+    InvalidSetting { key: &'static str, reason: String },
+
+    #[error("Invalid feed entry #{index} ('{name}'): {reason}")]
+    InvalidFeed {
+        index: usize,
+        name: String,
+        reason: String,
+    },
 }
@@ -0,0 +1,225 @@
+use crate::config::{ArchiveSettings, ArchiveStorage};
+use crate::db::payloads::{
+    ArchivedPayload, get_raw_payload, insert_raw_payload, record_feed_payload,
+};
+use crate::error::ArchiveError;
+use chrono::{DateTime, Utc};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Content-addressed store of raw response bodies. Identical bodies are
+/// kept once; every fetch is indexed in `warehouse.feed_payloads`.
+#[derive(Debug, Clone)]
+pub struct PayloadArchive {
+    pool: PgPool,
+    settings: ArchiveSettings,
+}
+
+impl PayloadArchive {
+    pub fn new(pool: PgPool, settings: ArchiveSettings) -> Self {
+        Self { pool, settings }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.settings.storage != ArchiveStorage::Off
+    }
+
+    /// Archive the raw body fetched from `feed`, noting the encoding it was
+    /// decoded from; returns its SHA-256, or `None` when archiving is off
+    pub async fn store(
+        &self,
+        feed: &str,
+        fetched_at: DateTime<Utc>,
+        body: &[u8],
+        encoding: &str,
+    ) -> Result<Option<String>, ArchiveError> {
+        let storage = self.settings.storage;
+        let sha256 = content_address(body);
+        let size_bytes = body.len() as i64;
+
+        match storage {
+            ArchiveStorage::Off => return Ok(None),
+            ArchiveStorage::Postgres => {
+                insert_raw_payload(&self.pool, &sha256, size_bytes, &compress(body)).await?;
+            }
+            ArchiveStorage::Disk => {
+                let dir = self
+                    .settings
+                    .dir
+                    .as_deref()
+                    .ok_or(ArchiveError::NoDirectory)?;
+                write_file(&payload_path(dir, &sha256), &compress(body)).await?;
+            }
+        }
+        record_feed_payload(
+            &self.pool,
+            feed,
+            fetched_at,
+            &sha256,
+            storage.as_str(),
+            size_bytes,
+            encoding,
+        )
+        .await?;
+
+        Ok(Some(sha256))
+    }
+
+    /// The original body of an archived fetch, checked against its address
+    pub async fn load(&self, payload: &ArchivedPayload) -> Result<Vec<u8>, ArchiveError> {
+        let gzip = match payload.storage.as_str() {
+            "disk" => {
+                let dir = self
+                    .settings
+                    .dir
+                    .as_deref()
+                    .ok_or(ArchiveError::NoDirectory)?;
+                let path = payload_path(dir, &payload.sha256);
+                match tokio::fs::read(&path).await {
+                    Ok(gzip) => gzip,
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                        return Err(ArchiveError::Missing(payload.sha256.clone()));
+                    }
+                    Err(source) => {
+                        return Err(ArchiveError::Io {
+                            path: path.display().to_string(),
+                            source,
+                        });
+                    }
+                }
+            }
+            _ => get_raw_payload(&self.pool, &payload.sha256)
+                .await?
+                .ok_or_else(|| ArchiveError::Missing(payload.sha256.clone()))?,
+        };
+
+        let body = decompress(&gzip).map_err(|e| ArchiveError::Corrupt {
+            sha256: payload.sha256.clone(),
+            reason: e.to_string(),
+        })?;
+        if content_address(&body) != payload.sha256 {
+            return Err(ArchiveError::Corrupt {
+                sha256: payload.sha256.clone(),
+                reason: "checksum mismatch".to_string(),
+            });
+        }
+        Ok(body)
+    }
+}
+
+pub fn content_address(body: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(body))
+}
+
+/// `<dir>/ab/abcdef….gz`, so no directory grows too large
+fn payload_path(dir: &Path, sha256: &str) -> PathBuf {
+    dir.join(&sha256[..2]).join(format!("{sha256}.gz"))
+}
+
+fn compress(body: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(body)
+        .expect("writing to a Vec cannot fail");
+    encoder.finish().expect("writing to a Vec cannot fail")
+}
+
+fn decompress(gzip: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut body = Vec::new();
+    GzDecoder::new(gzip).read_to_end(&mut body)?;
+    Ok(body)
+}
+
+/// Content-addressed files never change: an existing file is kept, and new
+/// ones appear atomically via a rename
+async fn write_file(path: &Path, contents: &[u8]) -> Result<(), ArchiveError> {
+    let io_error = |source| ArchiveError::Io {
+        path: path.display().to_string(),
+        source,
+    };
+
+    if tokio::fs::try_exists(path).await.map_err(io_error)? {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await.map_err(io_error)?;
+    }
+    let tmp = path.with_extension(format!("tmp{}", std::process::id()));
+    tokio::fs::write(&tmp, contents).await.map_err(io_error)?;
+    tokio::fs::rename(&tmp, path).await.map_err(io_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::create_pg_pool;
+    use crate::db::payloads::list_feed_payloads;
+    use chrono::{Duration, SubsecRound};
+    use dotenvy::dotenv;
+
+    #[test]
+    fn test_compression_round_trip() {
+        let body = b"<rss><channel><title>t</title></channel></rss>".repeat(50);
+        let gzip = compress(&body);
+        assert!(gzip.len() < body.len());
+        assert_eq!(decompress(&gzip).unwrap(), body);
+        assert_eq!(content_address(&body).len(), 64);
+    }
+
+    #[tokio::test]
+    async fn test_archive_store_and_load() {
+        dotenv().ok();
+
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = create_pg_pool(&database_url).await;
+
+        let dir = std::env::temp_dir().join(format!("raven_archive_{}", uuid::Uuid::new_v4()));
+        let fetched_at = Utc::now().trunc_subsecs(6);
+
+        for storage in [ArchiveStorage::Postgres, ArchiveStorage::Disk] {
+            let archive = PayloadArchive::new(
+                pool.clone(),
+                ArchiveSettings {
+                    storage,
+                    dir: Some(dir.clone()),
+                },
+            );
+            let feed = format!("archive_{}", uuid::Uuid::new_v4());
+            let body = format!("<rss><!-- {feed} --></rss>");
+
+            // The same body twice: two fetches, one stored copy
+            let first = archive
+                .store(&feed, fetched_at, body.as_bytes(), "UTF-8")
+                .await
+                .unwrap();
+            let second = archive
+                .store(&feed, fetched_at, body.as_bytes(), "UTF-8")
+                .await
+                .unwrap();
+            assert_eq!(first, second);
+
+            let payloads =
+                list_feed_payloads(&pool, &feed, fetched_at, fetched_at + Duration::seconds(1))
+                    .await
+                    .unwrap();
+            assert_eq!(payloads.len(), 2);
+            assert_eq!(payloads[0].storage, storage.as_str());
+            assert_eq!(payloads[0].encoding.as_deref(), Some("UTF-8"));
+            assert_eq!(archive.load(&payloads[1]).await.unwrap(), body.as_bytes());
+        }
+
+        let off = PayloadArchive::new(pool, ArchiveSettings::default());
+        assert!(!off.is_enabled());
+        assert_eq!(
+            off.store("off", fetched_at, b"x", "UTF-8").await.unwrap(),
+            None
+        );
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
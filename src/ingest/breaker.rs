@@ -0,0 +1,152 @@
+use crate::config::BreakerSettings;
+use serde::Serialize;
+use std::fmt;
+use tokio::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakerState {
+    /// Feed is healthy and polled normally
+    #[default]
+    Closed,
+    /// Feed failed repeatedly and is skipped until the cooldown expires
+    Open,
+    /// Cooldown expired; the next poll decides whether to close or reopen
+    HalfOpen,
+}
+
+impl fmt::Display for BreakerState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half-open",
+        })
+    }
+}
+
+/// Per-feed circuit breaker, kept across ingestion cycles
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    state: BreakerState,
+    consecutive_failures: u32,
+    cooldown: Duration,
+    open_until: Option<Instant>,
+    settings: BreakerSettings,
+}
+
+impl CircuitBreaker {
+    pub fn new(settings: BreakerSettings) -> Self {
+        Self {
+            state: BreakerState::Closed,
+            consecutive_failures: 0,
+            cooldown: Duration::from_secs(settings.cooldown_secs),
+            open_until: None,
+            settings,
+        }
+    }
+
+    pub fn state(&self) -> BreakerState {
+        self.state
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+
+    /// Whether the feed may be polled now. Moves an expired open breaker
+    /// into half-open so a single trial request goes through.
+    pub fn allow(&mut self, now: Instant) -> bool {
+        match self.state {
+            BreakerState::Closed | BreakerState::HalfOpen => true,
+            BreakerState::Open => match self.open_until {
+                Some(until) if now < until => false,
+                _ => {
+                    self.state = BreakerState::HalfOpen;
+                    true
+                }
+            },
+        }
+    }
+
+    /// Time left before an open breaker allows a trial request
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        match self.state {
+            BreakerState::Open => self
+                .open_until
+                .map(|until| until.saturating_duration_since(now)),
+            _ => None,
+        }
+    }
+
+    pub fn record_success(&mut self) {
+        self.state = BreakerState::Closed;
+        self.consecutive_failures = 0;
+        self.cooldown = Duration::from_secs(self.settings.cooldown_secs);
+        self.open_until = None;
+    }
+
+    pub fn record_failure(&mut self, now: Instant) {
+        self.consecutive_failures += 1;
+
+        match self.state {
+            // The trial request failed: back off twice as long
+            BreakerState::HalfOpen => {
+                let max = Duration::from_secs(self.settings.max_cooldown_secs);
+                self.cooldown = (self.cooldown * 2).min(max);
+                self.open(now);
+            }
+            BreakerState::Closed
+                if self.consecutive_failures >= self.settings.failure_threshold =>
+            {
+                self.open(now);
+            }
+            _ => {}
+        }
+    }
+
+    fn open(&mut self, now: Instant) {
+        self.state = BreakerState::Open;
+        self.open_until = Some(now + self.cooldown);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> BreakerSettings {
+        BreakerSettings {
+            failure_threshold: 2,
+            cooldown_secs: 60,
+            max_cooldown_secs: 100,
+        }
+    }
+
+    #[test]
+    fn test_breaker_transitions() {
+        let start = Instant::now();
+        let mut breaker = CircuitBreaker::new(settings());
+
+        breaker.record_failure(start);
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        breaker.record_failure(start);
+        assert_eq!(breaker.state(), BreakerState::Open);
+        assert!(!breaker.allow(start + Duration::from_secs(30)));
+
+        // Cooldown expired: one trial request
+        assert!(breaker.allow(start + Duration::from_secs(61)));
+        assert_eq!(breaker.state(), BreakerState::HalfOpen);
+
+        // Trial failed: reopen with a doubled (capped) cooldown
+        let reopened = start + Duration::from_secs(61);
+        breaker.record_failure(reopened);
+        assert_eq!(breaker.state(), BreakerState::Open);
+        assert_eq!(breaker.remaining(reopened), Some(Duration::from_secs(100)));
+
+        assert!(breaker.allow(reopened + Duration::from_secs(100)));
+        breaker.record_success();
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        assert_eq!(breaker.consecutive_failures(), 0);
+    }
+}
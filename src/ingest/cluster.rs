@@ -0,0 +1,211 @@
+use crate::config::ClusterSettings;
+use crate::db::clusters::{
+    ItemClustering, SignedItem, list_signed_items, list_unclustered_items, save_clustering,
+};
+use crate::rss::signature::StorySignature;
+use chrono::{DateTime, TimeDelta, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Unclustered items handled per pass
+const BATCH_SIZE: i64 = 2000;
+
+/// Totals of one clustering run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClusterReport {
+    /// Items that got a signature
+    pub signed: usize,
+    /// Of those, items that joined or started a story cluster
+    pub clustered: usize,
+    pub new_clusters: usize,
+}
+
+/// An item that new ones are compared against
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub id: Uuid,
+    pub published_at: DateTime<Utc>,
+    pub cluster_id: Option<Uuid>,
+    pub signature: StorySignature,
+    /// Signed in this run, so the signature still has to be written
+    pub signed_now: bool,
+    /// `cluster_id` changed in this run
+    pub assigned_now: bool,
+}
+
+impl From<SignedItem> for Candidate {
+    fn from(item: SignedItem) -> Self {
+        Self {
+            id: item.id,
+            published_at: item.published_at,
+            cluster_id: item.cluster_id,
+            signature: StorySignature::from_parts(item.simhash as u64, &item.minhash),
+            signed_now: false,
+            assigned_now: false,
+        }
+    }
+}
+
+/// Whether two signatures are the same story
+pub fn is_match(a: &StorySignature, b: &StorySignature, settings: &ClusterSettings) -> bool {
+    !a.minhash.is_empty()
+        && !b.minhash.is_empty()
+        && (a.similarity(b) >= settings.min_similarity || a.distance(b) <= settings.max_distance)
+}
+
+/// Add `item` to `candidates`, joining the cluster of its most similar match
+/// within the time window; a match without a cluster starts one with it.
+/// Returns the new cluster, if one was created.
+pub fn assign(
+    candidates: &mut Vec<Candidate>,
+    mut item: Candidate,
+    settings: &ClusterSettings,
+) -> Option<Uuid> {
+    let window = TimeDelta::hours(settings.window_hours as i64);
+    let best = candidates
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.id != item.id && (c.published_at - item.published_at).abs() <= window)
+        .filter(|(_, c)| is_match(&c.signature, &item.signature, settings))
+        .max_by(|(_, a), (_, b)| {
+            let (a, b) = (
+                a.signature.similarity(&item.signature),
+                b.signature.similarity(&item.signature),
+            );
+            a.total_cmp(&b)
+        })
+        .map(|(index, _)| index);
+
+    let mut created = None;
+    if let Some(best) = best {
+        let matched = &mut candidates[best];
+        let cluster_id = *matched.cluster_id.get_or_insert_with(|| {
+            matched.assigned_now = true;
+            *created.insert(Uuid::new_v4())
+        });
+        item.cluster_id = Some(cluster_id);
+        item.assigned_now = true;
+    }
+    candidates.push(item);
+    created
+}
+
+/// Sign the unclustered items published since `since` and group them into
+/// stories with each other and with signed items of the surrounding window
+pub async fn cluster_items(
+    pool: &PgPool,
+    settings: &ClusterSettings,
+    since: DateTime<Utc>,
+) -> Result<ClusterReport, sqlx::Error> {
+    let window = TimeDelta::hours(settings.window_hours as i64);
+    let mut report = ClusterReport::default();
+
+    loop {
+        let pending = list_unclustered_items(pool, since, BATCH_SIZE).await?;
+        let (Some(first), Some(last)) = (pending.first(), pending.last()) else {
+            break;
+        };
+        let mut candidates: Vec<Candidate> = list_signed_items(
+            pool,
+            first.published_at - window,
+            last.published_at + window,
+        )
+        .await?
+        .into_iter()
+        .map(Candidate::from)
+        .collect();
+
+        let full_batch = pending.len() as i64 == BATCH_SIZE;
+        for item in pending {
+            let item = Candidate {
+                id: item.id,
+                published_at: item.published_at,
+                cluster_id: None,
+                signature: StorySignature::new(&item.title, item.summary.as_deref()),
+                signed_now: true,
+                assigned_now: false,
+            };
+            report.new_clusters += assign(&mut candidates, item, settings).iter().count();
+        }
+
+        let changes: Vec<ItemClustering> = candidates
+            .into_iter()
+            .filter(|c| c.signed_now || c.assigned_now)
+            .inspect(|c| {
+                report.signed += usize::from(c.signed_now);
+                report.clustered += usize::from(c.signed_now && c.cluster_id.is_some());
+            })
+            .map(|c| ItemClustering {
+                id: c.id,
+                signature: c
+                    .signed_now
+                    .then(|| (c.signature.simhash as i64, c.signature.minhash_bytes())),
+                cluster_id: c.cluster_id.filter(|_| c.assigned_now),
+            })
+            .collect();
+        save_clustering(pool, &changes).await?;
+
+        if !full_batch {
+            break;
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(title: &str, hours_ago: i64, now: DateTime<Utc>) -> Candidate {
+        Candidate {
+            id: Uuid::new_v4(),
+            published_at: now - TimeDelta::hours(hours_ago),
+            cluster_id: None,
+            signature: StorySignature::new(title, None),
+            signed_now: true,
+            assigned_now: false,
+        }
+    }
+
+    #[test]
+    fn test_assign_groups_matching_items_in_window() {
+        let settings = ClusterSettings::default();
+        let now = Utc::now();
+        let fed = "Fed holds rates steady and signals two cuts later this year";
+        let mut candidates = Vec::new();
+
+        assert_eq!(
+            assign(&mut candidates, candidate(fed, 3, now), &settings),
+            None
+        );
+        assert_eq!(
+            assign(
+                &mut candidates,
+                candidate("Bitcoin tops $100,000 for the first time", 2, now),
+                &settings
+            ),
+            None
+        );
+
+        // The second report of the Fed story starts a cluster with the first
+        let created = assign(&mut candidates, candidate(fed, 1, now), &settings);
+        assert!(created.is_some());
+        assert_eq!(candidates[0].cluster_id, created);
+        assert!(candidates[0].assigned_now);
+        assert_eq!(candidates[2].cluster_id, created);
+        assert_eq!(candidates[1].cluster_id, None);
+
+        // A third one joins it
+        assert_eq!(
+            assign(&mut candidates, candidate(fed, 0, now), &settings),
+            None
+        );
+        assert_eq!(candidates[3].cluster_id, created);
+
+        // Outside the window it's a different story
+        let mut later = vec![candidate(fed, 0, now)];
+        assign(&mut later, candidate(fed, 48, now), &settings);
+        assert_eq!(later[1].cluster_id, None);
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Bounds concurrent fetches globally and per host
+pub struct FetchLimiter {
+    global: Arc<Semaphore>,
+    per_host: usize,
+    hosts: Mutex<HashMap<String, Arc<Semaphore>>>,
+}
+
+/// Held for the duration of a fetch; both slots are released on drop
+pub struct FetchPermit {
+    _host: OwnedSemaphorePermit,
+    _global: OwnedSemaphorePermit,
+}
+
+impl FetchLimiter {
+    pub fn new(max_concurrency: usize, per_host_concurrency: usize) -> Self {
+        Self {
+            global: Arc::new(Semaphore::new(max_concurrency.max(1))),
+            per_host: per_host_concurrency.max(1),
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn host_semaphore(&self, host: &str) -> Arc<Semaphore> {
+        let mut hosts = self.hosts.lock().expect("limiter lock poisoned");
+        hosts
+            .entry(host.to_ascii_lowercase())
+            .or_insert_with(|| Arc::new(Semaphore::new(self.per_host)))
+            .clone()
+    }
+
+    /// Wait for a host slot first, then a global slot, so a busy host
+    /// never holds global capacity while queueing.
+    pub async fn acquire(&self, url: &str) -> FetchPermit {
+        let host = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+            .unwrap_or_default();
+
+        let host = self
+            .host_semaphore(&host)
+            .acquire_owned()
+            .await
+            .expect("host semaphore closed");
+        let global = self
+            .global
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("global semaphore closed");
+
+        FetchPermit {
+            _host: host,
+            _global: global,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_per_host_limit() {
+        let limiter = FetchLimiter::new(4, 1);
+
+        let first = limiter
+            .acquire("https://feeds.bloomberg.com/markets/news.rss")
+            .await;
+        // Other hosts are not blocked by a busy host
+        let _other = limiter.acquire("https://www.coindesk.com/rss").await;
+
+        let same_host = limiter.host_semaphore("feeds.bloomberg.com");
+        assert_eq!(same_host.available_permits(), 0);
+
+        drop(first);
+        assert_eq!(same_host.available_permits(), 1);
+        assert_eq!(limiter.global.available_permits(), 3);
+    }
+}
@@ -1,108 +1,581 @@
-use crate::db::insert_rss_item;
+pub mod archive;
+pub mod breaker;
+pub mod cluster;
+pub mod limiter;
+pub mod registry;
+pub mod report;
+pub mod retry;
+pub mod schedule;
+
+use crate::config::{Config, FeedOptions, IngestSettings, ParserKind};
+use crate::db::fetch_runs::record_fetch_run;
+use crate::db::http_cache::{
+    FeedValidators, get_feed_validators, record_not_modified, save_feed_validators,
+};
+use crate::db::payloads::list_feed_payloads;
+use crate::db::schedule::{list_feed_schedules, prune_feed_schedules, save_feed_schedule};
+use crate::db::{UpsertOutcome, insert_rss_items};
 use crate::error::RssIngestionError;
-use crate::rss::{
-    RssParser, bloomberg::BloombergRssParser, coindesk::CoindeskRssParser,
-    reuters::ReutersRssParser,
+use crate::rss::encoding::{DecodedPayload, charset_of, decode_payload};
+use crate::rss::html::SUMMARY_TEXT_MAX_CHARS;
+use crate::rss::link::LinkCanonicalizer;
+use crate::rss::{DiagnosticKind, ParseDiagnostic, RssItem, RssParser, detect_format};
+use archive::PayloadArchive;
+use breaker::CircuitBreaker;
+use chrono::{DateTime, TimeDelta, Utc};
+use cluster::cluster_items;
+use futures::future::join_all;
+use limiter::FetchLimiter;
+use registry::cycle_feeds;
+use reqwest::StatusCode;
+use reqwest::header::{
+    CONTENT_TYPE, ETAG, HeaderName, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
 };
+use retry::{RetryPolicy, retry};
+use schedule::{Scheduler, publish_cadence};
 use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::Mutex;
 use tokio::select;
-use tokio::time::{Duration, interval};
-use tracing::info;
+use tokio::time::{Duration, Instant, sleep};
+use tracing::{info, warn};
+
+pub use report::{CycleReport, FailurePolicy, FeedReport};
+
+pub const USER_AGENT: &str = concat!("raven-news/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug, Clone)]
+pub struct Feed {
+    pub name: String,
+    pub url: String,
+    pub parser: ParserKind,
+    pub active: bool,
+    pub options: FeedOptions,
+}
+
+impl Feed {
+    /// Source label stored on this feed's items
+    pub fn source(&self) -> &str {
+        self.parser
+            .fixed_source()
+            .or(self.options.source.as_deref())
+            .unwrap_or(&self.name)
+    }
+
+    pub fn build_parser(&self) -> Box<dyn RssParser> {
+        self.parser.build(self.source())
+    }
+}
 
-struct Feed {
+// Built-in feed list, used when no config file is given
+struct BuiltinFeed {
     name: &'static str,
     url: &'static str,
-    parser: &'static dyn RssParser,
+    parser: ParserKind,
     active: bool,
 }
 
-static BLOOMBERG: BloombergRssParser = BloombergRssParser;
-static COINDESK: CoindeskRssParser = CoindeskRssParser;
-static REUTERS: ReutersRssParser = ReutersRssParser;
-
-const FEEDS: [Feed; 7] = [
-    Feed {
+const FEEDS: [BuiltinFeed; 7] = [
+    BuiltinFeed {
         name: "bloomberg_wealth",
         url: "https://feeds.bloomberg.com/wealth/news.rss",
-        parser: &BLOOMBERG,
+        parser: ParserKind::Bloomberg,
         active: true,
     },
-    Feed {
+    BuiltinFeed {
         name: "bloomberg_economics",
         url: "https://feeds.bloomberg.com/economics/news.rss",
-        parser: &BLOOMBERG,
+        parser: ParserKind::Bloomberg,
         active: true,
     },
-    Feed {
+    BuiltinFeed {
         name: "bloomberg_markets",
         url: "https://feeds.bloomberg.com/markets/news.rss",
-        parser: &BLOOMBERG,
+        parser: ParserKind::Bloomberg,
         active: true,
     },
-    Feed {
+    BuiltinFeed {
         name: "coindesk",
         url: "https://www.coindesk.com/arc/outboundfeeds/rss",
-        parser: &COINDESK,
+        parser: ParserKind::Coindesk,
         active: true,
     },
-    Feed {
+    BuiltinFeed {
         name: "reuters_financial",
         url: "https://ir.thomsonreuters.com/rss/news-releases.xml?items=15",
-        parser: &REUTERS,
+        parser: ParserKind::Reuters,
         active: false, // Reuters financial no longer offers public free RSS feeds
     },
-    Feed {
+    BuiltinFeed {
         name: "reuters_events",
         url: "https://ir.thomsonreuters.com/rss/events.xml?items=15",
-        parser: &REUTERS,
+        parser: ParserKind::Reuters,
         active: false,
     },
-    Feed {
+    BuiltinFeed {
         name: "reuters_secfilings",
         url: "https://ir.thomsonreuters.com/rss/sec-filings.xml?items=15",
-        parser: &REUTERS,
+        parser: ParserKind::Reuters,
         active: false,
     },
 ];
 
-async fn fetch_and_insert(pool: &PgPool, feed: &Feed) -> Result<(), RssIngestionError> {
-    let xml = reqwest::get(feed.url).await?.text().await?;
-    let items = feed.parser.parse(&xml)?;
+pub fn default_feeds() -> Vec<Feed> {
+    FEEDS
+        .iter()
+        .map(|f| Feed {
+            name: f.name.to_string(),
+            url: f.url.to_string(),
+            parser: f.parser,
+            active: f.active,
+            options: FeedOptions::default(),
+        })
+        .collect()
+}
+
+/// Shared state for one or many ingestion cycles
+pub struct Ingestor {
+    pool: PgPool,
+    client: reqwest::Client,
+    limiter: FetchLimiter,
+    retry: RetryPolicy,
+    breakers: Mutex<HashMap<String, CircuitBreaker>>,
+    archive: PayloadArchive,
+    links: LinkCanonicalizer,
+    settings: IngestSettings,
+}
+
+enum FetchOutcome {
+    NotModified,
+    Body {
+        status: u16,
+        body: Vec<u8>,
+        /// `charset` of the `Content-Type` header
+        charset: Option<String>,
+        validators: FeedValidators,
+    },
+}
+
+impl Ingestor {
+    pub fn new(pool: PgPool, settings: IngestSettings) -> Result<Self, RssIngestionError> {
+        let client = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(settings.timeout_secs))
+            .build()?;
+        let archive = PayloadArchive::new(pool.clone(), settings.archive.clone());
+
+        Ok(Self {
+            pool,
+            client,
+            limiter: FetchLimiter::new(settings.max_concurrency, settings.per_host_concurrency),
+            retry: RetryPolicy::from(&settings.retry),
+            breakers: Mutex::new(HashMap::new()),
+            archive,
+            links: settings.links.canonicalizer(),
+            settings,
+        })
+    }
+
+    fn build_request(&self, feed: &Feed, validators: &FeedValidators) -> reqwest::RequestBuilder {
+        let mut request = self.client.get(&feed.url);
+        if let Some(secs) = feed.options.timeout_secs {
+            request = request.timeout(Duration::from_secs(secs));
+        }
+        if let Some(user_agent) = &feed.options.user_agent {
+            request = request.header(reqwest::header::USER_AGENT, user_agent);
+        }
+        if let Some(etag) = &validators.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+        request
+    }
+
+    async fn fetch(
+        &self,
+        feed: &Feed,
+        validators: &FeedValidators,
+    ) -> Result<FetchOutcome, RssIngestionError> {
+        let _permit = self.limiter.acquire(&feed.url).await;
+        let response = self.build_request(feed, validators).send().await?;
+
+        let status = response.status();
+        if status == StatusCode::NOT_MODIFIED {
+            return Ok(FetchOutcome::NotModified);
+        }
+        if !status.is_success() {
+            return Err(RssIngestionError::Http(status.as_u16()));
+        }
+
+        let validators = FeedValidators {
+            etag: header_value(&response, ETAG),
+            last_modified: header_value(&response, LAST_MODIFIED),
+        };
+        let charset = header_value(&response, CONTENT_TYPE)
+            .as_deref()
+            .and_then(charset_of)
+            .map(str::to_string);
+        Ok(FetchOutcome::Body {
+            status: status.as_u16(),
+            body: response.bytes().await?.to_vec(),
+            charset,
+            validators,
+        })
+    }
+
+    async fn fetch_and_insert(
+        &self,
+        feed: &Feed,
+        report: &mut FeedReport,
+    ) -> Result<(), RssIngestionError> {
+        let validators = get_feed_validators(&self.pool, &feed.name).await?;
+
+        let (attempts, outcome) =
+            retry(&self.retry, &feed.name, || self.fetch(feed, &validators)).await;
+        report.attempts = attempts;
+        if let Err(RssIngestionError::Http(status)) = &outcome {
+            report.http_status = Some(*status);
+        }
+
+        let (body, charset, fresh_validators) = match outcome? {
+            FetchOutcome::NotModified => {
+                report.http_status = Some(StatusCode::NOT_MODIFIED.as_u16());
+                report.not_modified = true;
+                record_not_modified(&self.pool, &feed.name).await?;
+                return Ok(());
+            }
+            FetchOutcome::Body {
+                status,
+                body,
+                charset,
+                validators,
+            } => {
+                report.http_status = Some(status);
+                report.bytes = Some(body.len());
+                (body, charset, validators)
+            }
+        };
+        report.fetched = true;
+
+        let payload = decode_payload(&body, charset.as_deref());
+        if let Err(e) = self
+            .archive
+            .store(
+                &feed.name,
+                report.started_at,
+                &body,
+                payload.encoding_name(),
+            )
+            .await
+        {
+            warn!(feed = %feed.name, "Failed to archive payload: {e}");
+        }
 
-    for item in &items {
+        let items = prepare_items(feed, &payload, &self.links, report)?;
         if feed.active {
-            insert_rss_item(pool, item).await?;
+            store_items(&self.pool, &items, report).await?;
+
+            // Only remember validators once the items are stored, so a failed
+            // parse or insert, or a feed polled while inactive, is retried
+            // with a full download.
+            if !fresh_validators.is_empty() || !validators.is_empty() {
+                save_feed_validators(&self.pool, &feed.name, &fresh_validators).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn ingest_feed(&self, feed: &Feed) -> FeedReport {
+        let mut report = FeedReport::new(&feed.name);
+
+        {
+            let mut breakers = self.breakers.lock().expect("breaker lock poisoned");
+            let breaker = breakers
+                .entry(feed.name.clone())
+                .or_insert_with(|| CircuitBreaker::new(self.settings.breaker.clone()));
+
+            let now = Instant::now();
+            if !breaker.allow(now) {
+                report.breaker = breaker.state();
+                report.short_circuited = true;
+                report.retry_in = breaker.remaining(now).map(|d| d.as_secs());
+            }
+        }
+        if report.short_circuited {
+            self.record_run(&mut report).await;
+            return report;
+        }
+
+        let result = self.fetch_and_insert(feed, &mut report).await;
+
+        {
+            let mut breakers = self.breakers.lock().expect("breaker lock poisoned");
+            if let Some(breaker) = breakers.get_mut(&feed.name) {
+                match &result {
+                    Ok(()) => breaker.record_success(),
+                    Err(err) if err.is_feed_failure() => breaker.record_failure(Instant::now()),
+                    Err(_) => {}
+                }
+                report.breaker = breaker.state();
+            }
+        }
+
+        if let Err(err) = result {
+            report.error = Some(err.to_string());
+        }
+        self.record_run(&mut report).await;
+        report
+    }
+
+    /// Close the report and log it to `warehouse.fetch_runs`; a failed write
+    /// is only logged so it never fails the feed
+    async fn record_run(&self, report: &mut FeedReport) {
+        report.finished_at = Utc::now();
+        if let Err(e) = record_fetch_run(&self.pool, &report.to_fetch_run()).await {
+            warn!(feed = %report.feed, "Failed to record fetch run: {e}");
         }
     }
 
+    /// Attempt every feed once, concurrently within the configured limits.
+    /// A failing feed is recorded in the report and never prevents the
+    /// remaining feeds from being polled.
+    pub async fn run_cycle(&self, feeds: &[Feed]) -> CycleReport {
+        let mut cycle = CycleReport::start();
+
+        cycle.feeds = join_all(feeds.iter().map(|feed| self.ingest_feed(feed))).await;
+        if self.settings.clusters.enabled {
+            self.cluster_recent_items().await;
+        }
+
+        cycle.finish();
+        cycle.log();
+        cycle
+    }
+
+    /// Group the items of the last clustering window into stories; a failure
+    /// is only logged, the items are picked up again next cycle
+    async fn cluster_recent_items(&self) {
+        let settings = &self.settings.clusters;
+        let since = Utc::now() - TimeDelta::hours(settings.window_hours as i64);
+        match cluster_items(&self.pool, settings, since).await {
+            Ok(report) => info!(
+                signed = report.signed,
+                clustered = report.clustered,
+                new_clusters = report.new_clusters,
+                "Story clustering finished"
+            ),
+            Err(e) => warn!("Failed to cluster stories: {e}"),
+        }
+    }
+
+    pub fn settings(&self) -> &IngestSettings {
+        &self.settings
+    }
+}
+
+/// Parse a payload into the items stored for `feed`, applying its date and
+/// identity policies and canonicalizing links
+fn prepare_items(
+    feed: &Feed,
+    payload: &DecodedPayload,
+    links: &LinkCanonicalizer,
+    report: &mut FeedReport,
+) -> Result<Vec<RssItem>, RssIngestionError> {
+    report.encoding = Some(payload.encoding_name().to_string());
+    report.format = detect_format(&payload.text);
+
+    let mut outcome = feed.build_parser().parse_outcome(&payload.text)?;
+    outcome.diagnostics.extend(payload.diagnostic());
+    for diagnostic in &outcome.diagnostics {
+        warn!(feed = %feed.name, "Parse warning: {diagnostic}");
+    }
+    report.skipped = outcome.skipped();
+    report.diagnostics = outcome.diagnostics.clone();
+    // Relative links resolve against the channel link, else the feed URL
+    let base_url = outcome.base_url.clone().unwrap_or_else(|| feed.url.clone());
+    let items = if feed.options.strict {
+        outcome.into_strict()?.items
+    } else {
+        outcome.items
+    };
+
+    report.parsed = items.len();
+    report.cadence_secs = publish_cadence(&items, Utc::now()).map(|d| d.as_secs());
+    report.undated = items.iter().filter(|i| i.published_at_fallback).count();
+    let mut items = feed.options.date_fallback.apply(items)?;
+    links.apply(&mut items, Some(&base_url));
+    feed.options.identity.assign(&mut items);
+    for item in &mut items {
+        item.feed = Some(feed.name.clone());
+        item.normalize_summary(SUMMARY_TEXT_MAX_CHARS);
+    }
+    Ok(items)
+}
+
+async fn store_items(
+    pool: &PgPool,
+    items: &[RssItem],
+    report: &mut FeedReport,
+) -> Result<(), RssIngestionError> {
+    let outcomes = insert_rss_items(pool, items).await?;
+    for (item, outcome) in items.iter().zip(outcomes) {
+        match outcome {
+            UpsertOutcome::Inserted => report.inserted += 1,
+            UpsertOutcome::Unchanged => report.skipped_duplicate += 1,
+            UpsertOutcome::Updated { .. } => report.updated += 1,
+            UpsertOutcome::Conflict { stored_id } => {
+                let diagnostic = ParseDiagnostic {
+                    item_index: None,
+                    byte_offset: None,
+                    kind: DiagnosticKind::LinkConflict {
+                        title: item.title.clone(),
+                        link: item.link.clone(),
+                    },
+                };
+                warn!(feed = %report.feed, %stored_id, "Storage warning: {diagnostic}");
+                report.conflicts += 1;
+                report.diagnostics.push(diagnostic);
+            }
+        }
+    }
     Ok(())
 }
 
-pub async fn fetch_all_and_insert(pool: &PgPool) -> Result<(), RssIngestionError> {
-    for feed in &FEEDS {
-        if let Err(err) = fetch_and_insert(pool, feed).await {
-            return Err(RssIngestionError::Other(format!(
-                "Feed '{}' failed: {}",
-                feed.name, err
-            )));
+/// Totals of re-running the current parser over archived payloads
+#[derive(Debug, Clone, Default)]
+pub struct ReparseReport {
+    pub feed: String,
+    pub payloads: usize,
+    /// Payloads that could not be loaded or parsed
+    pub failed: usize,
+    pub parsed: usize,
+    /// Entries the parser dropped for lacking a required field
+    pub skipped: usize,
+    pub inserted: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+}
+
+/// Re-parse the payloads `feed` returned in `[since, until)` and upsert the
+/// items, so fields a parser used to drop are recovered. Undated items are
+/// dated by their fetch time. With `dry_run` nothing is written.
+pub async fn reparse_feed(
+    pool: &PgPool,
+    archive: &PayloadArchive,
+    links: &LinkCanonicalizer,
+    feed: &Feed,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+    dry_run: bool,
+) -> Result<ReparseReport, RssIngestionError> {
+    let payloads = list_feed_payloads(pool, &feed.name, since, until).await?;
+    let mut totals = ReparseReport {
+        feed: feed.name.clone(),
+        payloads: payloads.len(),
+        ..Default::default()
+    };
+
+    for payload in &payloads {
+        let mut report = FeedReport::new(&feed.name);
+        let items = archive
+            .load(payload)
+            .await
+            .map_err(RssIngestionError::from)
+            .and_then(|body| {
+                // Payloads archived before encodings were recorded hold UTF-8
+                let encoding = payload.encoding.as_deref().unwrap_or("utf-8");
+                prepare_items(
+                    feed,
+                    &decode_payload(&body, Some(encoding)),
+                    links,
+                    &mut report,
+                )
+            });
+        let mut items = match items {
+            Ok(items) => items,
+            Err(e) => {
+                warn!(feed = %feed.name, fetched_at = %payload.fetched_at, "Failed to reparse payload: {e}");
+                totals.failed += 1;
+                continue;
+            }
+        };
+        for item in items.iter_mut().filter(|i| i.published_at_fallback) {
+            item.published_at = payload.fetched_at;
         }
+
+        if !dry_run {
+            store_items(pool, &items, &mut report).await?;
+        }
+        totals.parsed += report.parsed;
+        totals.skipped += report.skipped;
+        totals.inserted += report.inserted;
+        totals.updated += report.updated;
+        totals.unchanged += report.skipped_duplicate;
     }
 
-    Ok(())
+    Ok(totals)
 }
 
-pub async fn run_scheduler(pool: PgPool) {
-    let mut ticker = interval(Duration::from_secs(60));
+fn header_value(response: &reqwest::Response, name: HeaderName) -> Option<String> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+pub async fn fetch_all_and_insert(
+    pool: &PgPool,
+    config: &Config,
+) -> Result<CycleReport, RssIngestionError> {
+    let ingestor = Ingestor::new(pool.clone(), config.ingest.clone())?;
+    let feeds = cycle_feeds(pool, &config.feeds).await;
+    Ok(ingestor.run_cycle(&feeds).await)
+}
+
+/// Upper bound for the scheduler's sleep, so registry changes apply promptly
+const MAX_IDLE: Duration = Duration::from_secs(60);
+
+pub async fn run_scheduler(pool: PgPool, config: Config) -> Result<(), RssIngestionError> {
+    let mut scheduler = Scheduler::new(config.ingest.schedule.clone());
+    let ingestor = Ingestor::new(pool.clone(), config.ingest)?;
+    match list_feed_schedules(&pool).await {
+        Ok(records) => scheduler.restore(records),
+        Err(e) => warn!("Failed to restore feed schedules: {e}"),
+    }
 
     info!("Ingestion scheduler started. Press Ctrl+C to stop.");
     loop {
-        select! {
-            _ = ticker.tick() => {
-                info!("Running scheduled RSS fetch...");
-                if let Err(e) = fetch_all_and_insert(&pool).await {
-                    eprintln!("Error fetching RSS: {e}");
+        // The registry is re-read every pass so feed changes apply without a restart
+        let feeds = cycle_feeds(&pool, &config.feeds).await;
+        scheduler.sync(&feeds, Utc::now());
+        let names: Vec<String> = feeds.iter().map(|f| f.name.clone()).collect();
+        if let Err(e) = prune_feed_schedules(&pool, &names).await {
+            warn!("Failed to prune feed schedules: {e}");
+        }
+
+        let due = scheduler.due(&feeds, Utc::now());
+        if !due.is_empty() {
+            info!(feeds = due.len(), "Running scheduled RSS fetch...");
+            let cycle = ingestor.run_cycle(&due).await;
+            for report in &cycle.feeds {
+                let Some(schedule) = scheduler.record(report, cycle.finished_at) else {
+                    continue;
+                };
+                if let Err(e) = save_feed_schedule(&pool, &schedule.to_record(&report.feed)).await {
+                    warn!(feed = %report.feed, "Failed to save feed schedule: {e}");
                 }
             }
+        }
+
+        let wait = scheduler
+            .wait(Utc::now())
+            .map_or(MAX_IDLE, |wait| wait.min(MAX_IDLE));
+        select! {
+            _ = sleep(wait) => {}
             _ = tokio::signal::ctrl_c() => {
                 info!("Shutdown signal received. Stopping ingestion scheduler...");
                 break;
@@ -110,4 +583,40 @@ pub async fn run_scheduler(pool: PgPool) {
         }
     }
     info!("Ingestion scheduler stopped.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::create_pg_pool;
+    use dotenvy::dotenv;
+
+    #[tokio::test]
+    async fn test_build_request_sends_validators() {
+        dotenv().ok();
+
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = create_pg_pool(&database_url).await;
+        let ingestor = Ingestor::new(pool, IngestSettings::default()).unwrap();
+        let feed = default_feeds().remove(0);
+
+        let request = ingestor
+            .build_request(&feed, &FeedValidators::default())
+            .build()
+            .unwrap();
+        assert!(request.headers().get(IF_NONE_MATCH).is_none());
+        assert!(request.headers().get(IF_MODIFIED_SINCE).is_none());
+
+        let validators = FeedValidators {
+            etag: Some("\"abc\"".to_string()),
+            last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string()),
+        };
+        let request = ingestor.build_request(&feed, &validators).build().unwrap();
+        assert_eq!(request.headers()[IF_NONE_MATCH], "\"abc\"");
+        assert_eq!(
+            request.headers()[IF_MODIFIED_SINCE],
+            "Wed, 21 Oct 2015 07:28:00 GMT"
+        );
+    }
 }
@@ -0,0 +1,187 @@
+use crate::config::{FeedEntry, FeedOptions, identity_conflict};
+use crate::db::feeds::{FeedRecord, NewFeedRecord, list_feeds};
+use crate::ingest::Feed;
+use sqlx::PgPool;
+use tracing::warn;
+
+/// Where the effective definition of a feed comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedOrigin {
+    /// Config file, or the built-in list when no file is given
+    Config,
+    /// A `warehouse.feeds` row
+    Registry,
+}
+
+impl FeedOrigin {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Config => "config",
+            Self::Registry => "registry",
+        }
+    }
+}
+
+/// Validate a registry row the same way as a `[[feeds]]` entry
+pub fn feed_from_record(record: &FeedRecord) -> Result<Feed, String> {
+    let mut options: FeedOptions = serde_json::from_value(record.options.clone())
+        .map_err(|e| format!("invalid options: {e}"))?;
+    options.interval_secs = record
+        .interval_secs
+        .and_then(|secs| u64::try_from(secs).ok());
+
+    FeedEntry {
+        name: record.name.clone(),
+        url: record.url.clone(),
+        parser: record.parser.clone(),
+        active: record.active,
+        options,
+    }
+    .validate()
+}
+
+/// Row for `insert_feed`; the interval lives in its own column
+pub fn record_from_feed(feed: &Feed) -> NewFeedRecord {
+    let mut options = feed.options.clone();
+    let interval_secs = options
+        .interval_secs
+        .take()
+        .and_then(|secs| i32::try_from(secs).ok());
+
+    NewFeedRecord {
+        name: feed.name.clone(),
+        url: feed.url.clone(),
+        parser: feed.parser.as_str().to_string(),
+        active: feed.active,
+        interval_secs,
+        options: serde_json::to_value(&options).expect("FeedOptions always serializes"),
+    }
+}
+
+/// Overlay registry rows on the config feeds: a row replaces the config
+/// feed of the same name and new names are appended. Invalid rows are
+/// skipped with a warning so one bad row can't stop ingestion.
+pub fn merge_feeds(config_feeds: &[Feed], records: &[FeedRecord]) -> Vec<(Feed, FeedOrigin)> {
+    let mut feeds: Vec<(Feed, FeedOrigin)> = config_feeds
+        .iter()
+        .map(|feed| (feed.clone(), FeedOrigin::Config))
+        .collect();
+
+    for record in records {
+        let feed = match feed_from_record(record) {
+            Ok(feed) => feed,
+            Err(reason) => {
+                warn!(feed = %record.name, "Skipping invalid registry feed: {reason}");
+                continue;
+            }
+        };
+        if let Some(reason) = identity_conflict(feeds.iter().map(|(f, _)| f), &feed) {
+            warn!(feed = %record.name, "Skipping registry feed: {reason}");
+            continue;
+        }
+
+        match feeds.iter_mut().find(|(f, _)| f.name == feed.name) {
+            Some(slot) => *slot = (feed, FeedOrigin::Registry),
+            None => feeds.push((feed, FeedOrigin::Registry)),
+        }
+    }
+
+    feeds
+}
+
+/// Config feeds merged with the current `warehouse.feeds` rows
+pub async fn resolve_feeds(
+    pool: &PgPool,
+    config_feeds: &[Feed],
+) -> Result<Vec<(Feed, FeedOrigin)>, sqlx::Error> {
+    let records = list_feeds(pool).await?;
+    Ok(merge_feeds(config_feeds, &records))
+}
+
+/// Feeds for one ingestion cycle; falls back to the config feeds when the
+/// registry can't be read (e.g. migration 106 not applied yet)
+pub async fn cycle_feeds(pool: &PgPool, config_feeds: &[Feed]) -> Vec<Feed> {
+    match resolve_feeds(pool, config_feeds).await {
+        Ok(feeds) => feeds.into_iter().map(|(feed, _)| feed).collect(),
+        Err(e) => {
+            warn!("Failed to load feed registry, using configured feeds only: {e}");
+            config_feeds.to_vec()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ParserKind;
+    use crate::rss::identity::IdentityStrategy;
+    use chrono::Utc;
+
+    fn config_feed(name: &str) -> Feed {
+        FeedEntry {
+            name: name.to_string(),
+            url: format!("https://example.com/{name}.xml"),
+            parser: "rss".to_string(),
+            active: true,
+            options: FeedOptions::default(),
+        }
+        .validate()
+        .unwrap()
+    }
+
+    fn record(feed: &Feed) -> FeedRecord {
+        let new = record_from_feed(feed);
+        FeedRecord {
+            name: new.name,
+            url: new.url,
+            parser: new.parser,
+            active: new.active,
+            interval_secs: new.interval_secs,
+            options: new.options,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_record_round_trip() {
+        let mut feed = config_feed("fed");
+        feed.parser = ParserKind::Atom;
+        feed.options.interval_secs = Some(900);
+        feed.options.identity = IdentityStrategy::Guid;
+
+        let stored = record(&feed);
+        assert_eq!(stored.interval_secs, Some(900));
+        assert!(stored.options.get("interval_secs").unwrap().is_null());
+
+        let loaded = feed_from_record(&stored).unwrap();
+        assert_eq!(loaded.parser, ParserKind::Atom);
+        assert_eq!(loaded.options.interval_secs, Some(900));
+        assert_eq!(loaded.options.identity, IdentityStrategy::Guid);
+    }
+
+    #[test]
+    fn test_merge_feeds() {
+        let config = vec![config_feed("a"), config_feed("b")];
+
+        let mut paused = config_feed("b");
+        paused.active = false;
+        let added = config_feed("c");
+        let mut broken = record(&config_feed("d"));
+        broken.parser = "nope".to_string();
+
+        let merged = merge_feeds(&config, &[record(&paused), record(&added), broken]);
+        let summary: Vec<(&str, bool, FeedOrigin)> = merged
+            .iter()
+            .map(|(f, origin)| (f.name.as_str(), f.active, *origin))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("a", true, FeedOrigin::Config),
+                ("b", false, FeedOrigin::Registry),
+                ("c", true, FeedOrigin::Registry),
+            ]
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use crate::db::fetch_runs::FetchRun;
+use crate::ingest::breaker::BreakerState;
+use crate::rss::{FeedFormat, ParseDiagnostic};
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use serde::Serialize;
+use tracing::{info, warn};
+
+/// Outcome of a single feed within one ingestion cycle
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FeedReport {
+    pub feed: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub fetched: bool,
+    /// Status of the last HTTP response, if one arrived
+    pub http_status: Option<u16>,
+    /// Size of the downloaded body
+    pub bytes: Option<usize>,
+    /// The server answered 304 Not Modified; nothing was parsed
+    pub not_modified: bool,
+    /// Encoding the body was decoded from (see `decode_payload`)
+    pub encoding: Option<String>,
+    /// Format sniffed from the payload
+    pub format: Option<FeedFormat>,
+    pub parsed: usize,
+    /// Entries the parser dropped for lacking a required field
+    pub skipped: usize,
+    /// Per-entry parse warnings, skipped entries included
+    pub diagnostics: Vec<ParseDiagnostic>,
+    /// Parsed items without a usable publish date (see `DateFallback`)
+    pub undated: usize,
+    pub inserted: usize,
+    /// Known items whose content changed; the old version became a revision
+    pub updated: usize,
+    pub skipped_duplicate: usize,
+    /// Items not stored because another item has their canonical link
+    pub conflicts: usize,
+    /// HTTP attempts made, including retries of transient errors
+    pub attempts: u32,
+    /// Circuit breaker state after this cycle
+    pub breaker: BreakerState,
+    /// The feed was skipped because its circuit breaker is open
+    pub short_circuited: bool,
+    /// Seconds until an open breaker lets a trial request through
+    pub retry_in: Option<u64>,
+    /// Publish cadence in seconds observed in the payload (see `publish_cadence`)
+    pub cadence_secs: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl FeedReport {
+    pub fn new(feed: impl Into<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            feed: feed.into(),
+            started_at: now,
+            finished_at: now,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        self.error.is_some()
+    }
+
+    /// The `warehouse.fetch_runs` row for this poll
+    pub fn to_fetch_run(&self) -> FetchRun {
+        let count = |n: usize| i32::try_from(n).unwrap_or(i32::MAX);
+        FetchRun {
+            feed: self.feed.clone(),
+            started_at: self.started_at,
+            finished_at: self.finished_at,
+            attempts: self.attempts as i32,
+            http_status: self.http_status.map(i32::from),
+            bytes: self.bytes.map(|b| b as i64),
+            not_modified: self.not_modified,
+            short_circuited: self.short_circuited,
+            parsed: count(self.parsed),
+            skipped: count(self.skipped),
+            diagnostics: (!self.diagnostics.is_empty()).then(|| {
+                serde_json::to_value(&self.diagnostics).expect("diagnostics always serialize")
+            }),
+            inserted: count(self.inserted),
+            updated: count(self.updated),
+            encoding: self.encoding.clone(),
+            error: self.error.clone(),
+        }
+    }
+}
+
+/// Outcome of one pass over every configured feed
+#[derive(Debug, Clone, Serialize)]
+pub struct CycleReport {
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub feeds: Vec<FeedReport>,
+}
+
+impl CycleReport {
+    pub fn start() -> Self {
+        let now = Utc::now();
+        Self {
+            started_at: now,
+            finished_at: now,
+            feeds: Vec::new(),
+        }
+    }
+
+    pub fn finish(&mut self) {
+        self.finished_at = Utc::now();
+    }
+
+    pub fn failed(&self) -> usize {
+        self.feeds.iter().filter(|f| f.is_failure()).count()
+    }
+
+    pub fn short_circuited(&self) -> usize {
+        self.feeds.iter().filter(|f| f.short_circuited).count()
+    }
+
+    pub fn not_modified(&self) -> usize {
+        self.feeds.iter().filter(|f| f.not_modified).count()
+    }
+
+    pub fn inserted(&self) -> usize {
+        self.feeds.iter().map(|f| f.inserted).sum()
+    }
+
+    pub fn updated(&self) -> usize {
+        self.feeds.iter().map(|f| f.updated).sum()
+    }
+
+    /// Log one line per feed followed by a cycle summary
+    pub fn log(&self) {
+        for feed in &self.feeds {
+            match &feed.error {
+                Some(err) => warn!(
+                    feed = %feed.feed,
+                    fetched = feed.fetched,
+                    attempts = feed.attempts,
+                    breaker = %feed.breaker,
+                    "Feed failed: {err}"
+                ),
+                None if feed.short_circuited => warn!(
+                    feed = %feed.feed,
+                    breaker = %feed.breaker,
+                    retry_in_secs = feed.retry_in,
+                    "Feed skipped: circuit breaker open"
+                ),
+                None if feed.not_modified => info!(feed = %feed.feed, "Feed not modified"),
+                None => info!(
+                    feed = %feed.feed,
+                    format = feed.format.map(|f| f.as_str()).unwrap_or("unknown"),
+                    encoding = feed.encoding.as_deref().unwrap_or("unknown"),
+                    parsed = feed.parsed,
+                    skipped = feed.skipped,
+                    undated = feed.undated,
+                    inserted = feed.inserted,
+                    updated = feed.updated,
+                    skipped_duplicate = feed.skipped_duplicate,
+                    conflicts = feed.conflicts,
+                    "Feed ingested"
+                ),
+            }
+        }
+
+        let elapsed = self.finished_at - self.started_at;
+        info!(
+            feeds = self.feeds.len(),
+            failed = self.failed(),
+            short_circuited = self.short_circuited(),
+            not_modified = self.not_modified(),
+            inserted = self.inserted(),
+            updated = self.updated(),
+            elapsed_ms = elapsed.num_milliseconds(),
+            "Ingestion cycle finished"
+        );
+    }
+}
+
+/// When a one-shot run should be considered failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FailurePolicy {
+    /// Fail if any feed failed
+    Any,
+    /// Fail only if every feed failed
+    All,
+    /// Never fail because of feed errors
+    Never,
+}
+
+impl FailurePolicy {
+    pub fn is_failure(&self, report: &CycleReport) -> bool {
+        let failed = report.failed();
+        match self {
+            Self::Any => failed > 0,
+            Self::All => failed > 0 && failed == report.feeds.len(),
+            Self::Never => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report_with(errors: &[bool]) -> CycleReport {
+        let mut report = CycleReport::start();
+        for (i, failed) in errors.iter().enumerate() {
+            let mut feed = FeedReport::new(format!("feed_{i}"));
+            if *failed {
+                feed.error = Some("HTTP 503".to_string());
+            }
+            report.feeds.push(feed);
+        }
+        report.finish();
+        report
+    }
+
+    #[test]
+    fn test_failure_policy() {
+        let partial = report_with(&[false, true, false]);
+        assert!(FailurePolicy::Any.is_failure(&partial));
+        assert!(!FailurePolicy::All.is_failure(&partial));
+        assert!(!FailurePolicy::Never.is_failure(&partial));
+
+        let total = report_with(&[true, true]);
+        assert!(FailurePolicy::All.is_failure(&total));
+
+        let healthy = report_with(&[false, false]);
+        assert!(!FailurePolicy::Any.is_failure(&healthy));
+        assert!(!FailurePolicy::All.is_failure(&report_with(&[])));
+    }
+}
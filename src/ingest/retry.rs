@@ -0,0 +1,129 @@
+use crate::config::RetrySettings;
+use crate::error::RssIngestionError;
+use rand::Rng;
+use std::future::Future;
+use tokio::time::{Duration, sleep};
+use tracing::warn;
+
+/// Capped exponential backoff with full jitter
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl From<&RetrySettings> for RetryPolicy {
+    fn from(settings: &RetrySettings) -> Self {
+        Self {
+            max_attempts: settings.max_attempts.max(1),
+            base_delay: Duration::from_millis(settings.base_delay_ms),
+            max_delay: Duration::from_millis(settings.max_delay_ms),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Upper bound of the delay after the given (1-based) failed attempt
+    pub fn ceiling(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+
+    /// Random delay in `[0, ceiling(attempt)]`
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let ceiling = self.ceiling(attempt).as_millis() as u64;
+        Duration::from_millis(rand::rng().random_range(0..=ceiling))
+    }
+}
+
+/// Run `op` until it succeeds, fails with a non-transient error, or the
+/// attempt budget is spent. Returns the number of attempts made.
+pub async fn retry<T, F, Fut>(
+    policy: &RetryPolicy,
+    feed: &str,
+    mut op: F,
+) -> (u32, Result<T, RssIngestionError>)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, RssIngestionError>>,
+{
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Err(err) if err.is_transient() && attempt < policy.max_attempts => {
+                let delay = policy.backoff(attempt);
+                warn!(
+                    feed,
+                    attempt,
+                    delay_ms = delay.as_millis() as u64,
+                    "Transient fetch error, retrying: {err}"
+                );
+                sleep(delay).await;
+                attempt += 1;
+            }
+            result => return (attempt, result),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(4),
+        }
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(5),
+        };
+        assert_eq!(policy.ceiling(1), Duration::from_millis(500));
+        assert_eq!(policy.ceiling(2), Duration::from_millis(1000));
+        assert_eq!(policy.ceiling(8), Duration::from_secs(5));
+        assert_eq!(policy.ceiling(64), Duration::from_secs(5));
+        assert!(policy.backoff(3) <= Duration::from_millis(2000));
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_errors() {
+        let mut calls = 0;
+        let (attempts, result) = retry(&policy(), "test", || {
+            calls += 1;
+            let fail = calls < 3;
+            async move {
+                if fail {
+                    Err(RssIngestionError::Http(503))
+                } else {
+                    Ok(calls)
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(attempts, 3);
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_stops_on_permanent_error() {
+        let (attempts, result): (u32, Result<(), _>) = retry(&policy(), "test", || async {
+            Err(RssIngestionError::Http(404))
+        })
+        .await;
+
+        assert_eq!(attempts, 1);
+        assert!(result.is_err());
+    }
+}
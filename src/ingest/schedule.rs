@@ -0,0 +1,393 @@
+use crate::config::ScheduleSettings;
+use crate::db::schedule::FeedScheduleRecord;
+use crate::ingest::Feed;
+use crate::ingest::report::FeedReport;
+use crate::rss::RssItem;
+use chrono::{DateTime, TimeDelta, Utc};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Newest publish dates considered when measuring a feed's cadence
+const CADENCE_WINDOW: usize = 20;
+
+/// Typical time between publications of a feed: the median gap between its
+/// newest dated items, or the time since the newest one when that is longer,
+/// so a feed that went quiet is polled less often. `None` with fewer than two
+/// dated items.
+pub fn publish_cadence(items: &[RssItem], now: DateTime<Utc>) -> Option<Duration> {
+    let mut dates: Vec<DateTime<Utc>> = items
+        .iter()
+        .filter(|item| !item.published_at_fallback)
+        .map(|item| item.published_at)
+        .collect();
+    if dates.len() < 2 {
+        return None;
+    }
+    dates.sort_unstable_by(|a, b| b.cmp(a));
+    dates.truncate(CADENCE_WINDOW);
+
+    let mut gaps: Vec<i64> = dates
+        .windows(2)
+        .map(|pair| (pair[0] - pair[1]).num_seconds())
+        .collect();
+    gaps.sort_unstable();
+    let median = gaps[gaps.len() / 2];
+    let quiet = (now - dates[0]).num_seconds();
+
+    Some(Duration::from_secs(median.max(quiet).max(0) as u64))
+}
+
+/// Move `current` halfway towards polling twice per publish cadence, within
+/// the configured bounds. Halving damps one-off bursts or lulls.
+pub fn next_interval(
+    current: Duration,
+    cadence: Duration,
+    settings: &ScheduleSettings,
+) -> Duration {
+    let target = cadence / 2;
+    ((current + target) / 2).clamp(
+        Duration::from_secs(settings.min_interval_secs),
+        Duration::from_secs(settings.max_interval_secs),
+    )
+}
+
+/// Polling state of one feed
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedSchedule {
+    pub interval: Duration,
+    pub adaptive: bool,
+    /// Publish cadence observed at the last poll
+    pub cadence: Option<Duration>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub next_run_at: DateTime<Utc>,
+}
+
+impl FeedSchedule {
+    pub fn to_record(&self, feed: &str) -> FeedScheduleRecord {
+        FeedScheduleRecord {
+            feed: feed.to_string(),
+            interval_secs: secs_i32(self.interval),
+            adaptive: self.adaptive,
+            cadence_secs: self.cadence.map(secs_i32),
+            last_run_at: self.last_run_at,
+            next_run_at: self.next_run_at,
+        }
+    }
+}
+
+impl From<FeedScheduleRecord> for FeedSchedule {
+    fn from(record: FeedScheduleRecord) -> Self {
+        Self {
+            interval: Duration::from_secs(record.interval_secs.max(1) as u64),
+            adaptive: record.adaptive,
+            cadence: record
+                .cadence_secs
+                .map(|secs| Duration::from_secs(secs.max(0) as u64)),
+            last_run_at: record.last_run_at,
+            next_run_at: record.next_run_at,
+        }
+    }
+}
+
+/// Decides which feeds are due; fixed feeds keep their configured interval,
+/// adaptive feeds follow their publish cadence
+#[derive(Debug, Clone)]
+pub struct Scheduler {
+    settings: ScheduleSettings,
+    feeds: HashMap<String, FeedSchedule>,
+}
+
+impl Scheduler {
+    pub fn new(settings: ScheduleSettings) -> Self {
+        Self {
+            settings,
+            feeds: HashMap::new(),
+        }
+    }
+
+    /// Seed from persisted rows so intervals and next polls survive a restart
+    pub fn restore(&mut self, records: impl IntoIterator<Item = FeedScheduleRecord>) {
+        for record in records {
+            self.feeds.insert(record.feed.clone(), record.into());
+        }
+    }
+
+    pub fn get(&self, feed: &str) -> Option<&FeedSchedule> {
+        self.feeds.get(feed)
+    }
+
+    fn is_adaptive(&self, feed: &Feed) -> bool {
+        feed.options.adaptive.unwrap_or(self.settings.adaptive)
+    }
+
+    fn bounds(&self) -> (Duration, Duration) {
+        (
+            Duration::from_secs(self.settings.min_interval_secs),
+            Duration::from_secs(self.settings.max_interval_secs),
+        )
+    }
+
+    /// Configured interval; the starting point of adaptive feeds
+    fn base_interval(&self, feed: &Feed, adaptive: bool) -> Duration {
+        let base = Duration::from_secs(
+            feed.options
+                .interval_secs
+                .unwrap_or(self.settings.interval_secs),
+        );
+        if adaptive {
+            let (min, max) = self.bounds();
+            base.clamp(min, max)
+        } else {
+            base
+        }
+    }
+
+    /// Track the current feed list: new feeds are due now, removed feeds are
+    /// forgotten, and a changed fixed interval or mode counts from the last run
+    pub fn sync(&mut self, feeds: &[Feed], now: DateTime<Utc>) {
+        let (min, max) = self.bounds();
+
+        for feed in feeds {
+            let adaptive = self.is_adaptive(feed);
+            let base = self.base_interval(feed, adaptive);
+
+            match self.feeds.get_mut(&feed.name) {
+                None => {
+                    self.feeds.insert(
+                        feed.name.clone(),
+                        FeedSchedule {
+                            interval: base,
+                            adaptive,
+                            cadence: None,
+                            last_run_at: None,
+                            next_run_at: now,
+                        },
+                    );
+                }
+                Some(schedule)
+                    if schedule.adaptive != adaptive
+                        || (!adaptive && schedule.interval != base) =>
+                {
+                    schedule.adaptive = adaptive;
+                    schedule.interval = base;
+                    schedule.next_run_at =
+                        schedule.last_run_at.map_or(now, |last| after(last, base));
+                }
+                Some(schedule) if adaptive => {
+                    schedule.interval = schedule.interval.clamp(min, max);
+                }
+                Some(_) => {}
+            }
+        }
+
+        self.feeds
+            .retain(|name, _| feeds.iter().any(|feed| &feed.name == name));
+    }
+
+    /// Feeds whose next run is not in the future
+    pub fn due(&self, feeds: &[Feed], now: DateTime<Utc>) -> Vec<Feed> {
+        feeds
+            .iter()
+            .filter(|feed| {
+                self.feeds
+                    .get(&feed.name)
+                    .is_some_and(|schedule| schedule.next_run_at <= now)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Plan the next run of a feed after a poll finished at `now`
+    pub fn record(&mut self, report: &FeedReport, now: DateTime<Utc>) -> Option<&FeedSchedule> {
+        let settings = &self.settings;
+        let schedule = self.feeds.get_mut(&report.feed)?;
+
+        schedule.last_run_at = Some(now);
+        if schedule.adaptive
+            && let Some(secs) = report.cadence_secs
+        {
+            let cadence = Duration::from_secs(secs);
+            schedule.cadence = Some(cadence);
+            schedule.interval = next_interval(schedule.interval, cadence, settings);
+        }
+
+        schedule.next_run_at = after(now, schedule.interval);
+        // No point in waking up before an open breaker allows a trial
+        if let Some(secs) = report.retry_in {
+            schedule.next_run_at = schedule
+                .next_run_at
+                .max(after(now, Duration::from_secs(secs)));
+        }
+
+        Some(schedule)
+    }
+
+    /// Time until the earliest next run, zero when a feed is overdue
+    pub fn wait(&self, now: DateTime<Utc>) -> Option<Duration> {
+        self.feeds
+            .values()
+            .map(|schedule| schedule.next_run_at)
+            .min()
+            .map(|next| (next - now).to_std().unwrap_or_default())
+    }
+}
+
+fn after(time: DateTime<Utc>, interval: Duration) -> DateTime<Utc> {
+    TimeDelta::from_std(interval)
+        .ok()
+        .and_then(|delta| time.checked_add_signed(delta))
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
+
+fn secs_i32(duration: Duration) -> i32 {
+    i32::try_from(duration.as_secs()).unwrap_or(i32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{FeedOptions, ParserKind};
+
+    fn settings() -> ScheduleSettings {
+        ScheduleSettings {
+            interval_secs: 60,
+            adaptive: false,
+            min_interval_secs: 60,
+            max_interval_secs: 3600,
+        }
+    }
+
+    fn feed(name: &str, interval_secs: Option<u64>, adaptive: Option<bool>) -> Feed {
+        Feed {
+            name: name.to_string(),
+            url: "https://example.com/rss".to_string(),
+            parser: ParserKind::Rss,
+            active: true,
+            options: FeedOptions {
+                interval_secs,
+                adaptive,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn item_at(published_at: DateTime<Utc>) -> RssItem {
+        RssItem::new(
+            "example",
+            "title",
+            "https://e.com",
+            None,
+            Some(published_at),
+        )
+    }
+
+    #[test]
+    fn test_publish_cadence() {
+        let now = Utc::now();
+        let minutes = |m: i64| now - TimeDelta::minutes(m);
+
+        // Every ten minutes, newest one just out
+        let busy: Vec<RssItem> = [0, 10, 20, 30, 41].map(|m| item_at(minutes(m))).into();
+        assert_eq!(publish_cadence(&busy, now), Some(Duration::from_secs(600)));
+
+        // Same rhythm, but nothing for two days: the silence wins
+        let quiet: Vec<RssItem> = [2880, 2890, 2900].map(|m| item_at(minutes(m))).into();
+        assert_eq!(
+            publish_cadence(&quiet, now),
+            Some(Duration::from_secs(2880 * 60))
+        );
+
+        let mut undated = item_at(now);
+        undated.published_at_fallback = true;
+        assert_eq!(publish_cadence(&[undated, item_at(now)], now), None);
+    }
+
+    #[test]
+    fn test_next_interval_is_damped_and_bounded() {
+        let settings = settings();
+        let current = Duration::from_secs(600);
+
+        // Publishes every 4 minutes: halfway towards 2-minute polls
+        assert_eq!(
+            next_interval(current, Duration::from_secs(240), &settings),
+            Duration::from_secs(360)
+        );
+        assert_eq!(
+            next_interval(current, Duration::ZERO, &settings),
+            Duration::from_secs(300)
+        );
+        assert_eq!(
+            next_interval(Duration::from_secs(60), Duration::ZERO, &settings),
+            Duration::from_secs(60)
+        );
+        assert_eq!(
+            next_interval(current, Duration::from_secs(86_400), &settings),
+            Duration::from_secs(3600)
+        );
+    }
+
+    #[test]
+    fn test_scheduler_runs_feeds_at_their_own_interval() {
+        let start = Utc::now();
+        let feeds = vec![feed("markets", None, None), feed("events", Some(600), None)];
+        let mut scheduler = Scheduler::new(settings());
+        scheduler.sync(&feeds, start);
+
+        // Everything is due on the first pass
+        assert_eq!(scheduler.due(&feeds, start).len(), 2);
+        for feed in &feeds {
+            scheduler.record(&FeedReport::new(&feed.name), start);
+        }
+        assert_eq!(scheduler.wait(start), Some(Duration::from_secs(60)));
+
+        let later = start + TimeDelta::seconds(61);
+        let due = scheduler.due(&feeds, later);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].name, "markets");
+        assert_eq!(
+            scheduler.get("events").unwrap().next_run_at,
+            start + TimeDelta::seconds(600)
+        );
+
+        // Lowering the interval counts from the last run; removed feeds go away
+        let feeds = vec![feed("events", Some(30), None)];
+        scheduler.sync(&feeds, later);
+        assert_eq!(
+            scheduler.get("events").unwrap().next_run_at,
+            start + TimeDelta::seconds(30)
+        );
+        assert!(scheduler.get("markets").is_none());
+    }
+
+    #[test]
+    fn test_scheduler_adapts_to_cadence() {
+        let start = Utc::now();
+        let feeds = vec![feed("markets", Some(600), Some(true))];
+        let mut scheduler = Scheduler::new(settings());
+        scheduler.sync(&feeds, start);
+
+        let mut report = FeedReport::new("markets");
+        report.cadence_secs = Some(120);
+        let schedule = scheduler.record(&report, start).unwrap();
+        assert_eq!(schedule.interval, Duration::from_secs(330));
+        assert_eq!(schedule.cadence, Some(Duration::from_secs(120)));
+        assert_eq!(schedule.next_run_at, start + TimeDelta::seconds(330));
+
+        // A restored schedule keeps the adapted interval
+        let record = schedule.to_record("markets");
+        let mut restarted = Scheduler::new(settings());
+        restarted.restore([record]);
+        restarted.sync(&feeds, start);
+        assert_eq!(
+            restarted.get("markets").unwrap().interval,
+            Duration::from_secs(330)
+        );
+
+        // Switching to a fixed interval resets it
+        let feeds = vec![feed("markets", Some(600), Some(false))];
+        restarted.sync(&feeds, start);
+        let schedule = restarted.get("markets").unwrap();
+        assert!(!schedule.adaptive);
+        assert_eq!(schedule.interval, Duration::from_secs(600));
+    }
+}
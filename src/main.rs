@@ -1,16 +1,45 @@
-use clap::{Parser, Subcommand};
+use chrono::{DateTime, SecondsFormat, TimeDelta, Utc};
+use clap::{Parser, Subcommand, ValueEnum};
 use dotenvy::dotenv;
+use raven_news::config::{
+    Config, FeedEntry, FeedOptions, ParserKind, identity_conflict, load_config,
+};
+use raven_news::db::clusters::{get_story_cluster, list_story_clusters};
 use raven_news::db::create_pg_pool;
+use raven_news::db::feeds::{delete_feed, get_feed, insert_feed, set_feed_active};
+use raven_news::db::fetch_runs::{feed_run_stats, list_fetch_runs};
+use raven_news::db::identity::backfill_item_ids;
+use raven_news::db::migrate::{MigrationStatus, migration_status, run_migrations};
+use raven_news::db::schedule::list_feed_schedules;
+use raven_news::db::stats::{
+    count_daily_rss_items, count_source_rss_items, count_total_rss_items, feed_cache_stats,
+};
+use raven_news::ingest::FailurePolicy;
+use raven_news::ingest::archive::PayloadArchive;
+use raven_news::ingest::cluster::cluster_items;
+use raven_news::ingest::registry::{FeedOrigin, record_from_feed, resolve_feeds};
+use raven_news::ingest::{Feed, USER_AGENT, fetch_all_and_insert, reparse_feed, run_scheduler};
+use raven_news::rss::date::{DateFallback, parse_date};
+use raven_news::rss::detect_format;
+use raven_news::rss::encoding::{charset_of, decode_payload};
+use raven_news::rss::html::SUMMARY_TEXT_MAX_CHARS;
+use raven_news::rss::identity::IdentityStrategy;
+use raven_news::rss::link::LinkCanonicalizer;
+use reqwest::header::CONTENT_TYPE;
 use sqlx::PgPool;
-use raven_news::db::stats::{count_daily_rss_items, count_source_rss_items, count_total_rss_items};
-use raven_news::ingest::{fetch_all_and_insert, run_scheduler};
+use std::path::PathBuf;
 use tracing::info;
 use tracing_subscriber::{EnvFilter, filter::Directive};
+use uuid::Uuid;
 
 #[derive(Parser)]
 #[command(name = "raven-news")]
 #[command(about = "RSS ingestion CLI")]
 struct Cli {
+    /// Path to a feeds.toml file (defaults to the built-in feed list)
+    #[arg(long, global = true, env = "RAVEN_CONFIG")]
+    config: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -18,10 +47,90 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Fetch RSS feeds one time and insert into DB (force snapshot)
-    FetchOnce,
+    FetchOnce {
+        /// Exit with a non-zero status when any / all feeds failed
+        #[arg(long, value_enum, default_value_t = FailurePolicy::Any, env = "RAVEN_FAIL_ON")]
+        fail_on: FailurePolicy,
+    },
+
+    /// Run continuous ingestion loop (each feed on its own interval)
+    Run {
+        /// Apply pending embedded migrations before starting
+        #[arg(long, env = "RAVEN_AUTO_MIGRATE")]
+        auto_migrate: bool,
+    },
+
+    /// Apply the database migrations embedded in this binary
+    #[command(args_conflicts_with_subcommands = true)]
+    Migrate {
+        /// List pending migrations without applying them
+        #[arg(long)]
+        dry_run: bool,
+
+        #[command(subcommand)]
+        action: Option<MigrateAction>,
+    },
+
+    /// Manage the feed registry (`warehouse.feeds`), read at every cycle
+    Feeds {
+        #[command(subcommand)]
+        action: FeedsAction,
+    },
+
+    /// Re-key stored items to each feed's `options.identity` strategy
+    BackfillIds {
+        /// Only backfill the source of this feed
+        #[arg(long)]
+        feed: Option<String>,
+        /// Report what would change without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
 
-    /// Run continuous ingestion loop (every 60 seconds)
-    Run,
+    /// Parse a feed file or URL and print the items; nothing is stored
+    Parse {
+        /// bloomberg, coindesk, reuters, rss, atom, json_feed or auto
+        #[arg(long, default_value = "auto")]
+        parser: String,
+        /// Read the payload from a file
+        #[arg(long, conflicts_with = "url", required_unless_present = "url")]
+        file: Option<PathBuf>,
+        /// Download the payload
+        #[arg(long)]
+        url: Option<String>,
+        /// Source label for items of the generic parsers
+        #[arg(long, default_value = "parse")]
+        source: String,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        /// Exit with an error on any parse warning
+        #[arg(long)]
+        strict: bool,
+        /// Decode with this charset, as if sent in `Content-Type` (a BOM still wins)
+        #[arg(long)]
+        charset: Option<String>,
+    },
+
+    /// Re-run the current parser over archived payloads and upsert the items
+    Reparse {
+        #[arg(long)]
+        feed: String,
+        /// Window start: a timestamp or an age such as `30m`, `24h`, `7d`
+        #[arg(long, value_parser = parse_since)]
+        since: DateTime<Utc>,
+        /// Window end (exclusive); defaults to now
+        #[arg(long, value_parser = parse_since)]
+        until: Option<DateTime<Utc>>,
+        /// Parse the payloads without writing items
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Near-duplicate items grouped into stories across feeds and sources
+    Clusters {
+        #[command(subcommand)]
+        action: ClustersAction,
+    },
 
     /// Show ingestion statistics
     Stats {
@@ -30,11 +139,144 @@ enum Commands {
     },
 }
 
+/// How `parse` prints items; warnings always go to stderr
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Table,
+    /// One JSON array
+    Json,
+    /// One JSON object per line
+    Ndjson,
+}
+
+#[derive(Subcommand)]
+enum FeedsAction {
+    /// List effective feeds (config merged with the registry)
+    List,
+    /// Show the effective definition of one feed
+    Show { name: String },
+    /// Register a feed; it is picked up on the next cycle
+    Add {
+        name: String,
+        url: String,
+        /// bloomberg, coindesk, reuters, rss, atom, json_feed or auto
+        #[arg(long, default_value = "auto")]
+        parser: String,
+        /// Register the feed paused
+        #[arg(long)]
+        inactive: bool,
+        /// Polling interval in seconds (the starting point when adaptive)
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..=i32::MAX as u64))]
+        interval_secs: Option<u64>,
+        /// Follow the feed's publish cadence (overrides `ingest.schedule.adaptive`)
+        #[arg(long)]
+        adaptive: Option<bool>,
+        /// Source label for items of the generic parsers (defaults to the name)
+        #[arg(long)]
+        source: Option<String>,
+        /// HTTP request timeout in seconds
+        #[arg(long)]
+        timeout_secs: Option<u64>,
+        #[arg(long)]
+        user_agent: Option<String>,
+        #[arg(long, value_enum)]
+        identity: Option<IdentityStrategy>,
+        #[arg(long, value_enum)]
+        date_fallback: Option<DateFallback>,
+        /// Fail polls on any parse warning (skipped item, bad date, bad text)
+        #[arg(long)]
+        strict: bool,
+    },
+    /// Resume a paused feed
+    Enable { name: String },
+    /// Pause a feed; configured feeds get a paused registry copy
+    Disable { name: String },
+    /// Delete a feed from the registry
+    Remove { name: String },
+}
+
+#[derive(Subcommand)]
+enum ClustersAction {
+    /// Cluster items that were never clustered, e.g. after an upgrade
+    Build {
+        /// Oldest publish time to cover: a timestamp or an age such as `7d`
+        #[arg(long, default_value = "7d", value_parser = parse_since)]
+        since: DateTime<Utc>,
+    },
+    /// Clusters with recent items, newest first
+    List {
+        /// Window start: a timestamp or an age such as `30m`, `24h`, `7d`
+        #[arg(long, default_value = "24h", value_parser = parse_since)]
+        since: DateTime<Utc>,
+        /// Only clusters with at least this many items
+        #[arg(long, default_value_t = 2)]
+        min_items: i32,
+    },
+    /// The items of a cluster, given its ID or the ID of one of its items
+    Show { id: Uuid },
+}
+
+#[derive(Subcommand)]
+enum MigrateAction {
+    /// Show every embedded migration and whether it has been applied
+    Status,
+}
+
 #[derive(Subcommand)]
 enum StatsCategory {
     Total,
     Daily,
-    Source { name: String },
+    Source {
+        name: String,
+    },
+    /// Conditional GET validators and 304 Not Modified counts per feed
+    Cache,
+    /// Polling interval and next scheduled run per feed (written by `run`)
+    Schedule,
+    /// Fetch run summary per feed: runs, failures, last success
+    Feeds {
+        /// Window start: a timestamp or an age such as `30m`, `24h`, `7d`
+        #[arg(long, default_value = "24h", value_parser = parse_since)]
+        since: DateTime<Utc>,
+    },
+    /// Every fetch run in a window, oldest first
+    Runs {
+        #[arg(long)]
+        feed: Option<String>,
+        /// Window start: a timestamp or an age such as `30m`, `24h`, `7d`
+        #[arg(long, default_value = "24h", value_parser = parse_since)]
+        since: DateTime<Utc>,
+        /// Window end (exclusive); defaults to now
+        #[arg(long, value_parser = parse_since)]
+        until: Option<DateTime<Utc>>,
+    },
+}
+
+/// A timestamp (anything `parse_date` accepts, UTC when no zone is given)
+/// or an age relative to now
+fn parse_since(value: &str) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    let age = value.char_indices().last().and_then(|(i, unit)| {
+        let delta: fn(i64) -> Option<TimeDelta> = match unit {
+            's' => TimeDelta::try_seconds,
+            'm' => TimeDelta::try_minutes,
+            'h' => TimeDelta::try_hours,
+            'd' => TimeDelta::try_days,
+            _ => return None,
+        };
+        Some((value[..i].parse::<i64>().ok()?, delta))
+    });
+
+    let Some((n, delta)) = age else {
+        return parse_date(value)
+            .ok_or_else(|| "expected a timestamp or an age such as 30m, 24h or 7d".to_string());
+    };
+    if n < 0 {
+        return Err(format!("age {value} is negative"));
+    }
+    delta(n)
+        .and_then(|age| Utc::now().checked_sub_signed(age))
+        .ok_or_else(|| format!("age {value} is out of range"))
 }
 
 // CLI entry point
@@ -46,21 +288,85 @@ async fn main() {
 
     let cli = Cli::parse();
 
+    // `parse` works offline: no config or database needed
+    let command = match cli.command {
+        Commands::Parse {
+            parser,
+            file,
+            url,
+            source,
+            format,
+            strict,
+            charset,
+        } => return handle_parse(&parser, file, url, &source, format, strict, charset).await,
+        command => command,
+    };
+
+    let config = load_config(cli.config.as_deref()).unwrap_or_else(|err| {
+        eprintln!("Failed to load feed config: {err}");
+        std::process::exit(1);
+    });
+
     let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
     let pool = create_pg_pool(&database_url).await;
 
-    match cli.command {
-        Commands::FetchOnce => handle_fetch_once(&pool).await,
-        Commands::Run => {
+    match command {
+        Commands::Parse { .. } => unreachable!("handled before connecting"),
+        Commands::FetchOnce { fail_on } => handle_fetch_once(&pool, &config, fail_on).await,
+        Commands::Run { auto_migrate } => {
+            if auto_migrate {
+                apply_migrations(&pool).await;
+            }
             info!("Running continuous fetch");
-            run_scheduler(pool).await;
+            if let Err(e) = run_scheduler(pool, config).await {
+                eprintln!("Failed to start ingestion scheduler: {e}");
+                std::process::exit(1);
+            }
         }
+        Commands::Migrate { dry_run, action } => match action {
+            Some(MigrateAction::Status) => print_migration_status(&pool).await,
+            None if dry_run => print_pending_migrations(&pool).await,
+            None => apply_migrations(&pool).await,
+        },
+        Commands::Feeds { action } => handle_feeds(&pool, &config, action).await,
+        Commands::BackfillIds { feed, dry_run } => {
+            handle_backfill_ids(&pool, &config, feed.as_deref(), dry_run).await
+        }
+        Commands::Reparse {
+            feed,
+            since,
+            until,
+            dry_run,
+        } => {
+            handle_reparse(
+                &pool,
+                &config,
+                &feed,
+                since,
+                until.unwrap_or_else(Utc::now),
+                dry_run,
+            )
+            .await
+        }
+        Commands::Clusters { action } => handle_clusters(&pool, &config, action).await,
         Commands::Stats { category } => {
             info!("Fetching total RSS items statistics");
             match category {
                 StatsCategory::Total => print_total_stats(&pool).await,
                 StatsCategory::Daily => print_daily_stats(&pool).await,
                 StatsCategory::Source { name } => print_source_stats(&pool, &name).await,
+                StatsCategory::Cache => print_cache_stats(&pool).await,
+                StatsCategory::Schedule => print_schedule_stats(&pool).await,
+                StatsCategory::Feeds { since } => print_feed_run_stats(&pool, since).await,
+                StatsCategory::Runs { feed, since, until } => {
+                    print_fetch_runs(
+                        &pool,
+                        feed.as_deref(),
+                        since,
+                        until.unwrap_or_else(Utc::now),
+                    )
+                    .await
+                }
             }
         }
     };
@@ -72,16 +378,539 @@ fn init_tracing() {
         std::process::exit(1);
     });
 
+    // Logs go to stderr so command output (e.g. `parse --format json`) stays clean
     tracing_subscriber::fmt()
         .with_env_filter(EnvFilter::from_default_env().add_directive(directive))
+        .with_writer(std::io::stderr)
         .init();
 }
 
-async fn handle_fetch_once(pool: &PgPool) {
+async fn handle_fetch_once(pool: &PgPool, config: &Config, fail_on: FailurePolicy) {
     info!("Running one-time fetch");
-    if let Err(e) = fetch_all_and_insert(pool).await {
-        eprintln!("Failed to fetch RSS feeds: {e}");
+    let report = match fetch_all_and_insert(pool, config).await {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Failed to fetch RSS feeds: {e}");
+            std::process::exit(1);
+        }
+    };
+    if fail_on.is_failure(&report) {
+        eprintln!(
+            "Failed to fetch RSS feeds: {} of {} feeds failed",
+            report.failed(),
+            report.feeds.len()
+        );
+        std::process::exit(1);
+    }
+}
+
+async fn apply_migrations(pool: &PgPool) {
+    info!("Applying embedded migrations");
+    if let Err(e) = run_migrations(pool).await {
+        eprintln!("Failed to apply migrations: {e}");
+        std::process::exit(1);
+    }
+    println!("Database schema is up to date");
+}
+
+async fn load_migration_status(pool: &PgPool) -> Vec<MigrationStatus> {
+    migration_status(pool).await.unwrap_or_else(|e| {
+        eprintln!("Failed to read migration status: {e}");
+        std::process::exit(1);
+    })
+}
+
+async fn print_migration_status(pool: &PgPool) {
+    println!("{:>8}  {:<40} APPLIED", "VERSION", "DESCRIPTION");
+    for m in load_migration_status(pool).await {
+        let applied = match (m.applied_at, m.checksum_mismatch) {
+            (Some(_), true) => "checksum mismatch".to_string(),
+            (Some(at), false) => at.to_rfc3339(),
+            (None, _) => "pending".to_string(),
+        };
+        println!("{:>8}  {:<40} {}", m.version, m.description, applied);
+    }
+}
+
+async fn print_pending_migrations(pool: &PgPool) {
+    let pending: Vec<MigrationStatus> = load_migration_status(pool)
+        .await
+        .into_iter()
+        .filter(MigrationStatus::is_pending)
+        .collect();
+
+    if pending.is_empty() {
+        println!("No pending migrations");
+    }
+    for m in pending {
+        println!("Would apply {} {}", m.version, m.description);
+    }
+}
+
+async fn load_feeds(pool: &PgPool, config: &Config) -> Vec<(Feed, FeedOrigin)> {
+    resolve_feeds(pool, &config.feeds)
+        .await
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to load feed registry: {e}");
+            std::process::exit(1);
+        })
+}
+
+async fn handle_feeds(pool: &PgPool, config: &Config, action: FeedsAction) {
+    match action {
+        FeedsAction::List => {
+            println!(
+                "{:<24} {:<10} {:<6} {:>8}  {:<8} URL",
+                "NAME", "PARSER", "ACTIVE", "INTERVAL", "ORIGIN"
+            );
+            for (feed, origin) in load_feeds(pool, config).await {
+                println!(
+                    "{:<24} {:<10} {:<6} {:>8}  {:<8} {}",
+                    feed.name,
+                    feed.parser.as_str(),
+                    if feed.active { "yes" } else { "no" },
+                    feed.options
+                        .interval_secs
+                        .map(|s| format!("{s}s"))
+                        .unwrap_or_else(|| "-".to_string()),
+                    origin.as_str(),
+                    feed.url
+                );
+            }
+        }
+        FeedsAction::Show { name } => {
+            let Some((feed, origin)) = load_feeds(pool, config)
+                .await
+                .into_iter()
+                .find(|(f, _)| f.name == name)
+            else {
+                eprintln!("Unknown feed: {name}");
+                std::process::exit(1);
+            };
+            println!("name:          {}", feed.name);
+            println!("url:           {}", feed.url);
+            println!("parser:        {}", feed.parser.as_str());
+            println!("source:        {}", feed.source());
+            println!("active:        {}", feed.active);
+            println!("origin:        {}", origin.as_str());
+            println!("identity:      {}", feed.options.identity.as_str());
+            println!("date_fallback: {}", feed.options.date_fallback.as_str());
+            if let Some(secs) = feed.options.interval_secs {
+                println!("interval_secs: {secs}");
+            }
+            if let Some(adaptive) = feed.options.adaptive {
+                println!("adaptive:      {adaptive}");
+            }
+            if feed.options.strict {
+                println!("strict:        true");
+            }
+            if let Some(secs) = feed.options.timeout_secs {
+                println!("timeout_secs:  {secs}");
+            }
+            if let Some(ua) = &feed.options.user_agent {
+                println!("user_agent:    {ua}");
+            }
+            if origin == FeedOrigin::Registry
+                && let Ok(Some(record)) = get_feed(pool, &name).await
+            {
+                println!("created_at:    {}", record.created_at.to_rfc3339());
+                println!("updated_at:    {}", record.updated_at.to_rfc3339());
+            }
+        }
+        FeedsAction::Add {
+            name,
+            url,
+            parser,
+            inactive,
+            interval_secs,
+            adaptive,
+            source,
+            timeout_secs,
+            user_agent,
+            identity,
+            date_fallback,
+            strict,
+        } => {
+            let entry = FeedEntry {
+                name,
+                url,
+                parser,
+                active: !inactive,
+                options: FeedOptions {
+                    timeout_secs,
+                    user_agent,
+                    source,
+                    date_fallback: date_fallback.unwrap_or_default(),
+                    identity: identity.unwrap_or_default(),
+                    interval_secs,
+                    adaptive,
+                    strict,
+                },
+            };
+            let feed = entry.validate().unwrap_or_else(|reason| {
+                eprintln!("Invalid feed: {reason}");
+                std::process::exit(1);
+            });
+
+            let current = load_feeds(pool, config).await;
+            if let Some(reason) = identity_conflict(current.iter().map(|(f, _)| f), &feed) {
+                eprintln!("Invalid feed: {reason}");
+                std::process::exit(1);
+            }
+            register_feed(pool, &feed).await;
+            println!("Registered feed {}", feed.name);
+            if current
+                .iter()
+                .any(|(f, origin)| f.name == feed.name && *origin == FeedOrigin::Config)
+            {
+                println!("Registry feed {} overrides the configured one", feed.name);
+            }
+        }
+        FeedsAction::Enable { name } => set_active(pool, config, &name, true).await,
+        FeedsAction::Disable { name } => set_active(pool, config, &name, false).await,
+        FeedsAction::Remove { name } => match delete_feed(pool, &name).await {
+            Ok(true) => println!("Removed feed {name} from the registry"),
+            Ok(false) if config.feeds.iter().any(|f| f.name == name) => {
+                eprintln!(
+                    "Feed {name} comes from the config; use `feeds disable {name}` to pause it"
+                );
+                std::process::exit(1);
+            }
+            Ok(false) => {
+                eprintln!("Unknown feed: {name}");
+                std::process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("Failed to remove feed {name}: {e}");
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
+async fn register_feed(pool: &PgPool, feed: &Feed) {
+    match insert_feed(pool, &record_from_feed(feed)).await {
+        Ok(true) => {}
+        Ok(false) => {
+            eprintln!("Feed {} is already registered", feed.name);
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to register feed {}: {e}", feed.name);
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn set_active(pool: &PgPool, config: &Config, name: &str, active: bool) {
+    let state = if active { "enabled" } else { "disabled" };
+    match set_feed_active(pool, name, active).await {
+        Ok(true) => println!("Feed {name} {state}"),
+        Ok(false) => {
+            // Configured feeds are paused / resumed through a registry copy
+            let Some(feed) = config.feeds.iter().find(|f| f.name == name) else {
+                eprintln!("Unknown feed: {name}");
+                std::process::exit(1);
+            };
+            let mut copy = feed.clone();
+            copy.active = active;
+            register_feed(pool, &copy).await;
+            println!("Feed {name} {state} (registry copy of the configured feed)");
+        }
+        Err(e) => {
+            eprintln!("Failed to update feed {name}: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn handle_parse(
+    parser: &str,
+    file: Option<PathBuf>,
+    url: Option<String>,
+    source: &str,
+    format: OutputFormat,
+    strict: bool,
+    charset: Option<String>,
+) {
+    let Some(kind) = ParserKind::from_name(parser) else {
+        eprintln!("Unknown parser '{parser}'");
+        std::process::exit(1);
+    };
+
+    let document_url = url.clone();
+    let (body, served_charset) = match (file, url) {
+        (Some(path), _) => {
+            let body = std::fs::read(&path).unwrap_or_else(|e| {
+                eprintln!("Failed to read {}: {e}", path.display());
+                std::process::exit(1);
+            });
+            (body, None)
+        }
+        (None, Some(url)) => download(&url).await.unwrap_or_else(|e| {
+            eprintln!("Failed to download {url}: {e}");
+            std::process::exit(1);
+        }),
+        (None, None) => unreachable!("clap requires --file or --url"),
+    };
+    let payload = decode_payload(&body, charset.or(served_charset).as_deref());
+
+    let detected = detect_format(&payload.text);
+    let outcome = kind
+        .build(source)
+        .parse_outcome(&payload.text)
+        .map(|mut outcome| {
+            outcome.diagnostics.extend(payload.diagnostic());
+            outcome
+        })
+        .and_then(|outcome| {
+            if strict {
+                outcome.into_strict()
+            } else {
+                Ok(outcome)
+            }
+        })
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to parse payload: {e}");
+            std::process::exit(1);
+        });
+
+    eprintln!(
+        "parser: {}, format: {}, encoding: {} (from {}), items: {}, skipped: {}",
+        kind.as_str(),
+        detected.map(|f| f.as_str()).unwrap_or("unknown"),
+        payload.encoding_name(),
+        payload.source,
+        outcome.items.len(),
+        outcome.skipped()
+    );
+    for diagnostic in &outcome.diagnostics {
+        eprintln!("warning: {diagnostic}");
+    }
+    let base_url = outcome.base_url.or(document_url);
+    let mut items = outcome.items;
+    LinkCanonicalizer::default().apply(&mut items, base_url.as_deref());
+    for item in &mut items {
+        item.normalize_summary(SUMMARY_TEXT_MAX_CHARS);
+    }
+
+    match format {
+        OutputFormat::Table => {
+            println!("{:<20}  {:<60}  LINK", "PUBLISHED", "TITLE");
+            for item in &items {
+                let published = if item.published_at_fallback {
+                    "-".to_string()
+                } else {
+                    format_time(Some(item.published_at))
+                };
+                println!(
+                    "{published:<20}  {:<60}  {}",
+                    truncate(&item.title, 60),
+                    item.canonical_link.as_deref().unwrap_or(&item.link)
+                );
+            }
+        }
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&items).expect("RssItem always serializes")
+        ),
+        OutputFormat::Ndjson => {
+            for item in &items {
+                println!(
+                    "{}",
+                    serde_json::to_string(item).expect("RssItem always serializes")
+                );
+            }
+        }
+    }
+}
+
+/// The body and the `charset` of its `Content-Type`
+async fn download(url: &str) -> Result<(Vec<u8>, Option<String>), reqwest::Error> {
+    let response = reqwest::Client::builder()
+        .user_agent(USER_AGENT)
+        .build()?
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?;
+    let charset = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(charset_of)
+        .map(str::to_string);
+    Ok((response.bytes().await?.to_vec(), charset))
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max - 1) {
+        Some((end, _)) if text[end..].chars().count() > 1 => format!("{}…", &text[..end]),
+        _ => text.to_string(),
+    }
+}
+
+async fn handle_reparse(
+    pool: &PgPool,
+    config: &Config,
+    name: &str,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+    dry_run: bool,
+) {
+    let Some((feed, _)) = load_feeds(pool, config)
+        .await
+        .into_iter()
+        .find(|(f, _)| f.name == name)
+    else {
+        eprintln!("Unknown feed: {name}");
         std::process::exit(1);
+    };
+
+    info!("Reparsing archived payloads of {name} between {since} and {until}");
+    let archive = PayloadArchive::new(pool.clone(), config.ingest.archive.clone());
+    let links = config.ingest.links.canonicalizer();
+    match reparse_feed(pool, &archive, &links, &feed, since, until, dry_run).await {
+        Ok(report) => {
+            println!(
+                "{}{}: {} payloads ({} failed), parsed {}, skipped {}, inserted {}, updated {}, unchanged {}",
+                if dry_run { "[dry run] " } else { "" },
+                report.feed,
+                report.payloads,
+                report.failed,
+                report.parsed,
+                report.skipped,
+                report.inserted,
+                report.updated,
+                report.unchanged
+            );
+            if report.failed > 0 {
+                std::process::exit(1);
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to reparse {name}: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn handle_clusters(pool: &PgPool, config: &Config, action: ClustersAction) {
+    match action {
+        ClustersAction::Build { since } => {
+            info!("Clustering items published since {since}");
+            match cluster_items(pool, &config.ingest.clusters, since).await {
+                Ok(report) => println!(
+                    "signed {} items, {} clustered, {} new clusters",
+                    report.signed, report.clustered, report.new_clusters
+                ),
+                Err(e) => {
+                    eprintln!("Failed to cluster items: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        ClustersAction::List { since, min_items } => {
+            match list_story_clusters(pool, since, min_items).await {
+                Ok(clusters) => {
+                    println!(
+                        "{:<36}  {:>5}  {:<20}  {:<24}  TITLE",
+                        "CLUSTER", "ITEMS", "LAST PUBLISHED", "SOURCES"
+                    );
+                    for cluster in &clusters {
+                        println!(
+                            "{:<36}  {:>5}  {:<20}  {:<24}  {}",
+                            cluster.id,
+                            cluster.item_count,
+                            format_time(cluster.last_published_at),
+                            truncate(&cluster.sources.join(","), 24),
+                            cluster.title.as_deref().unwrap_or("-")
+                        );
+                    }
+                    println!("{} clusters", clusters.len());
+                }
+                Err(e) => {
+                    eprintln!("Failed to list clusters: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        ClustersAction::Show { id } => match get_story_cluster(pool, id).await {
+            Ok(Some((cluster, items))) => {
+                println!("cluster:   {}", cluster.id);
+                println!("title:     {}", cluster.title.as_deref().unwrap_or("-"));
+                println!("items:     {}", cluster.item_count);
+                println!("sources:   {}", cluster.sources.join(", "));
+                println!(
+                    "published: {} .. {}",
+                    format_time(cluster.first_published_at),
+                    format_time(cluster.last_published_at)
+                );
+                println!();
+                println!(
+                    "{:<20}  {:<12}  {:<24}  {:<60}  LINK",
+                    "PUBLISHED", "SOURCE", "FEED", "TITLE"
+                );
+                for item in &items {
+                    println!(
+                        "{:<20}  {:<12}  {:<24}  {:<60}  {}",
+                        format_time(Some(item.published_at)),
+                        item.source,
+                        item.feed.as_deref().unwrap_or("-"),
+                        truncate(&item.title, 60),
+                        item.link
+                    );
+                }
+            }
+            Ok(None) => {
+                eprintln!("No cluster with ID {id}, and no clustered item with that ID");
+                std::process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("Failed to load cluster {id}: {e}");
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
+async fn handle_backfill_ids(pool: &PgPool, config: &Config, feed: Option<&str>, dry_run: bool) {
+    let feeds = load_feeds(pool, config).await;
+    let mut targets: Vec<(&str, IdentityStrategy)> = Vec::new();
+    for (f, _) in &feeds {
+        if feed.is_some_and(|name| name != f.name) {
+            continue;
+        }
+        let target = (f.source(), f.options.identity);
+        if !targets.contains(&target) {
+            targets.push(target);
+        }
+    }
+    if let (Some(name), true) = (feed, targets.is_empty()) {
+        eprintln!("Unknown feed: {name}");
+        std::process::exit(1);
+    }
+
+    for (source, strategy) in targets {
+        info!(
+            "Backfilling item IDs for source {source} ({})",
+            strategy.as_str()
+        );
+        match backfill_item_ids(pool, source, strategy, dry_run).await {
+            Ok(report) => println!(
+                "{}{}: scanned {}, re-keyed {}, merged {} ({})",
+                if dry_run { "[dry run] " } else { "" },
+                report.source,
+                report.scanned,
+                report.rekeyed,
+                report.merged,
+                strategy.as_str()
+            ),
+            Err(e) => {
+                eprintln!("Failed to backfill item IDs for source {source}: {e}");
+                std::process::exit(1);
+            }
+        }
     }
 }
 
@@ -117,3 +946,199 @@ async fn print_source_stats(pool: &PgPool, name: &str) {
         }
     }
 }
+
+async fn print_cache_stats(pool: &PgPool) {
+    info!("Fetching conditional GET statistics");
+    match feed_cache_stats(pool).await {
+        Ok(rows) => {
+            println!(
+                "{:<24} {:>5} {:>13} {:>12}  LAST 304",
+                "FEED", "ETAG", "LAST-MODIFIED", "304 COUNT"
+            );
+            for row in rows {
+                println!(
+                    "{:<24} {:>5} {:>13} {:>12}  {}",
+                    row.feed,
+                    if row.has_etag { "yes" } else { "no" },
+                    if row.has_last_modified { "yes" } else { "no" },
+                    row.not_modified_count,
+                    row.last_not_modified_at
+                        .map(|t| t.to_rfc3339())
+                        .unwrap_or_else(|| "-".to_string())
+                );
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to fetch conditional GET statistics: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn print_schedule_stats(pool: &PgPool) {
+    info!("Fetching feed schedules");
+    match list_feed_schedules(pool).await {
+        Ok(rows) => {
+            println!(
+                "{:<24} {:<8} {:>9} {:>9}  {:<25}  NEXT RUN",
+                "FEED", "MODE", "INTERVAL", "CADENCE", "LAST RUN"
+            );
+            for row in rows {
+                println!(
+                    "{:<24} {:<8} {:>9} {:>9}  {:<25}  {}",
+                    row.feed,
+                    if row.adaptive { "adaptive" } else { "fixed" },
+                    format!("{}s", row.interval_secs),
+                    row.cadence_secs
+                        .map(|s| format!("{s}s"))
+                        .unwrap_or_else(|| "-".to_string()),
+                    format_time(row.last_run_at),
+                    format_time(Some(row.next_run_at))
+                );
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to fetch feed schedules: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn format_time(time: Option<DateTime<Utc>>) -> String {
+    time.map(|t| t.to_rfc3339_opts(SecondsFormat::Secs, true))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+async fn print_feed_run_stats(pool: &PgPool, since: DateTime<Utc>) {
+    info!("Fetching fetch run statistics since {since}");
+    match feed_run_stats(pool, since).await {
+        Ok(rows) => {
+            println!(
+                "{:<24} {:>5} {:>6} {:>5} {:>8} {:>7}  {:<20}  {:<20}  LAST ERROR",
+                "FEED", "RUNS", "FAILED", "304", "INSERTED", "AVG MS", "LAST RUN", "LAST SUCCESS"
+            );
+            for row in rows {
+                println!(
+                    "{:<24} {:>5} {:>6} {:>5} {:>8} {:>7}  {:<20}  {:<20}  {}",
+                    row.feed,
+                    row.runs,
+                    row.failures,
+                    row.not_modified,
+                    row.inserted,
+                    row.avg_duration_ms
+                        .map(|ms| format!("{ms:.0}"))
+                        .unwrap_or_else(|| "-".to_string()),
+                    format_time(row.last_run_at),
+                    format_time(row.last_success_at),
+                    row.last_error.as_deref().unwrap_or("-")
+                );
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to fetch fetch run statistics: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn print_fetch_runs(
+    pool: &PgPool,
+    feed: Option<&str>,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) {
+    info!("Fetching fetch runs between {since} and {until}");
+    match list_fetch_runs(pool, feed, since, until).await {
+        Ok(runs) => {
+            println!(
+                "{:<20}  {:<24} {:>7} {:>8} {:>6} {:>8} {:>6} {:>7} {:>8} {:>7}  RESULT",
+                "STARTED",
+                "FEED",
+                "MS",
+                "ATTEMPTS",
+                "STATUS",
+                "BYTES",
+                "PARSED",
+                "SKIPPED",
+                "INSERTED",
+                "UPDATED"
+            );
+            for run in &runs {
+                let result = match &run.error {
+                    Some(err) => err.as_str(),
+                    None if run.short_circuited => "skipped (breaker open)",
+                    None if run.not_modified => "not modified",
+                    None => "ok",
+                };
+                println!(
+                    "{:<20}  {:<24} {:>7} {:>8} {:>6} {:>8} {:>6} {:>7} {:>8} {:>7}  {}",
+                    format_time(Some(run.started_at)),
+                    run.feed,
+                    run.duration_ms(),
+                    run.attempts,
+                    run.http_status
+                        .map(|s| s.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                    run.bytes
+                        .map(|b| b.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                    run.parsed,
+                    run.skipped,
+                    run.inserted,
+                    run.updated,
+                    result
+                );
+            }
+            println!(
+                "{} runs, {} failed",
+                runs.len(),
+                runs.iter().filter(|r| r.is_failure()).count()
+            );
+        }
+        Err(e) => {
+            eprintln!("Failed to fetch fetch runs: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_since_ages() {
+        let before = Utc::now();
+        let since = parse_since("24h").unwrap();
+        assert!(since >= before - TimeDelta::hours(24));
+        assert!(since <= Utc::now() - TimeDelta::hours(24));
+        assert_eq!(
+            parse_since("2024-03-01T12:00:00Z").unwrap(),
+            DateTime::parse_from_rfc3339("2024-03-01T12:00:00Z").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_since_rejects_bad_ages() {
+        assert_eq!(parse_since("-7d").unwrap_err(), "age -7d is negative");
+        assert_eq!(
+            parse_since("99999999999d").unwrap_err(),
+            "age 99999999999d is out of range"
+        );
+        assert_eq!(
+            parse_since("9223372036854775807s").unwrap_err(),
+            "age 9223372036854775807s is out of range"
+        );
+        assert!(parse_since("7w").is_err());
+    }
+
+    #[test]
+    fn test_migrate_dry_run_conflicts_with_action() {
+        assert!(Cli::try_parse_from(["raven-news", "migrate", "--dry-run"]).is_ok());
+        assert!(Cli::try_parse_from(["raven-news", "migrate", "status"]).is_ok());
+        let err = Cli::try_parse_from(["raven-news", "migrate", "--dry-run", "status"])
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
+}
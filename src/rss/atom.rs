@@ -0,0 +1,525 @@
+use crate::error::RssParseError;
+use crate::rss::date::parse_date;
+use crate::rss::xml::{ATOM_NS, attribute, namespace_of, push_content, take_text};
+use crate::rss::{
+    DiagnosticKind, ParseDiagnostic, ParseOutcome, RssCategory, RssItem, RssParser, RssResult,
+};
+use quick_xml::NsReader;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::ResolveResult;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AtomFeed {
+    pub id: Option<String>,
+    pub title: Option<AtomText>,
+    pub updated: Option<String>,
+    pub links: Vec<AtomLink>,
+    pub entries: Vec<AtomEntry>,
+    /// Text problems found while reading (see `push_content`)
+    pub diagnostics: Vec<ParseDiagnostic>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AtomEntry {
+    pub id: Option<String>,
+    pub title: Option<AtomText>,
+    pub links: Vec<AtomLink>,
+    pub published: Option<String>,
+    pub updated: Option<String>,
+    pub summary: Option<AtomText>,
+    pub content: Option<AtomText>,
+    pub authors: Vec<AtomPerson>,
+    pub categories: Vec<AtomCategory>,
+    /// Byte offset of the `<entry>` start tag in the payload
+    pub byte_offset: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AtomTextKind {
+    #[default]
+    Text,
+    Html,
+    Xhtml,
+}
+
+/// Atom text construct (`type="text|html|xhtml"`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AtomText {
+    pub kind: AtomTextKind,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AtomLink {
+    /// Absolute URL, resolved against the in-scope `xml:base`
+    pub href: String,
+    pub rel: String,
+    pub mime_type: Option<String>,
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AtomPerson {
+    pub name: String,
+    pub email: Option<String>,
+    pub uri: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AtomCategory {
+    pub term: String,
+    pub scheme: Option<String>,
+    pub label: Option<String>,
+}
+
+impl AtomEntry {
+    /// `rel="alternate"` link (the default relation), else the first link
+    pub fn alternate_link(&self) -> Option<&AtomLink> {
+        self.links
+            .iter()
+            .find(|l| l.rel == "alternate" && l.mime_type.as_deref().is_none_or(is_html))
+            .or_else(|| self.links.iter().find(|l| l.rel == "alternate"))
+            .or_else(|| self.links.first())
+    }
+
+    /// `published`, falling back to `updated`
+    pub fn date(&self) -> Option<&str> {
+        self.published.as_deref().or(self.updated.as_deref())
+    }
+
+    /// First element an item needs that the entry lacks
+    pub fn missing_field(&self) -> Option<&'static str> {
+        if self.title.is_none() {
+            Some("title")
+        } else if self.alternate_link().is_none() {
+            Some("link")
+        } else if self.date().is_none() {
+            Some("published")
+        } else {
+            None
+        }
+    }
+
+    pub fn into_rss_item(self, source: &str) -> Option<RssItem> {
+        let title = self.title.as_ref()?.value.clone();
+        let link = self.alternate_link()?.href.clone();
+        let date = self.date()?;
+        let published_at = parse_date(date);
+        let summary = self.summary.or(self.content).map(|t| t.value);
+
+        Some(
+            RssItem::new(source, title, link, summary, published_at)
+                .with_guid(self.id)
+                .with_authors(self.authors.into_iter().map(|a| a.name).collect())
+                .with_categories(
+                    self.categories
+                        .into_iter()
+                        .map(|c| RssCategory::new(c.term, c.scheme))
+                        .collect(),
+                ),
+        )
+    }
+}
+
+fn is_html(mime_type: &str) -> bool {
+    matches!(mime_type, "text/html" | "application/xhtml+xml")
+}
+
+/// Atom 1.0 parser; `source` is stored on every produced item
+#[derive(Debug, Clone)]
+pub struct AtomParser {
+    source: String,
+}
+
+impl AtomParser {
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+        }
+    }
+}
+
+impl RssParser for AtomParser {
+    fn parse_outcome(&self, xml: &str) -> RssResult<ParseOutcome> {
+        let feed = parse_feed(xml)?;
+
+        let base_url = feed
+            .links
+            .iter()
+            .find(|l| l.rel == "alternate")
+            .map(|l| l.href.clone());
+        let mut outcome = ParseOutcome::new(feed.diagnostics, base_url);
+        for (index, entry) in feed.entries.into_iter().enumerate() {
+            let offset = entry.byte_offset;
+            if let Some(field) = entry.missing_field() {
+                outcome.skip(index, offset, field);
+                continue;
+            }
+            let date = entry.date().unwrap_or_default().to_string();
+            if let Some(item) = entry.into_rss_item(&self.source) {
+                outcome.push(index, offset, item, &date);
+            }
+        }
+        Ok(outcome)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tag {
+    Feed,
+    Entry,
+    Id,
+    Title,
+    Link,
+    Updated,
+    Published,
+    Summary,
+    Content,
+    Author,
+    Name,
+    Email,
+    Uri,
+    Category,
+    Other,
+}
+
+fn classify(ns: &ResolveResult, e: &BytesStart) -> Tag {
+    let name = e.name();
+    let prefix = name.prefix();
+
+    match namespace_of(ns, prefix.as_ref().map(|p| p.as_ref())) {
+        // Undeclared default namespace: be lenient and treat it as Atom
+        Some(ATOM_NS) | None => match name.local_name().as_ref() {
+            b"feed" => Tag::Feed,
+            b"entry" => Tag::Entry,
+            b"id" => Tag::Id,
+            b"title" => Tag::Title,
+            b"link" => Tag::Link,
+            b"updated" => Tag::Updated,
+            b"published" => Tag::Published,
+            b"summary" => Tag::Summary,
+            b"content" => Tag::Content,
+            b"author" => Tag::Author,
+            b"name" => Tag::Name,
+            b"email" => Tag::Email,
+            b"uri" => Tag::Uri,
+            b"category" => Tag::Category,
+            _ => Tag::Other,
+        },
+        _ => Tag::Other,
+    }
+}
+
+fn text_kind(e: &BytesStart) -> AtomTextKind {
+    match attribute(e, b"type").as_deref().map(str::trim) {
+        Some("html") | Some("text/html") => AtomTextKind::Html,
+        Some("xhtml") | Some("application/xhtml+xml") => AtomTextKind::Xhtml,
+        _ => AtomTextKind::Text,
+    }
+}
+
+/// Resolve `href` against the in-scope base; unresolvable values are kept as-is
+fn resolve(base: Option<&Url>, href: &str) -> String {
+    let href = href.trim();
+    match base {
+        Some(base) => base
+            .join(href)
+            .map(|u| u.to_string())
+            .unwrap_or_else(|_| href.to_string()),
+        None => href.to_string(),
+    }
+}
+
+fn link_of(e: &BytesStart, base: Option<&Url>) -> Option<AtomLink> {
+    let href = attribute(e, b"href")?;
+    Some(AtomLink {
+        href: resolve(base, &href),
+        rel: attribute(e, b"rel").unwrap_or_else(|| "alternate".to_string()),
+        mime_type: attribute(e, b"type"),
+        title: attribute(e, b"title"),
+    })
+}
+
+fn category_of(e: &BytesStart) -> Option<AtomCategory> {
+    Some(AtomCategory {
+        term: attribute(e, b"term")?,
+        scheme: attribute(e, b"scheme"),
+        label: attribute(e, b"label"),
+    })
+}
+
+/// Drop the mandatory `<div xmlns="http://www.w3.org/1999/xhtml">` wrapper
+fn unwrap_xhtml_div(raw: &str) -> String {
+    let trimmed = raw.trim();
+    if trimmed.starts_with("<div")
+        && trimmed.ends_with("</div>")
+        && let Some(open_end) = trimmed.find('>')
+    {
+        return trimmed[open_end + 1..trimmed.len() - "</div>".len()]
+            .trim()
+            .to_string();
+    }
+    trimmed.to_string()
+}
+
+/// Parse an Atom 1.0 document into its feed metadata and entries
+pub fn parse_feed(xml: &str) -> RssResult<AtomFeed> {
+    let mut reader = NsReader::from_str(xml);
+
+    let mut feed = AtomFeed::default();
+    let mut stack: Vec<Tag> = Vec::new();
+    // Effective xml:base for each open element
+    let mut bases: Vec<Option<Url>> = Vec::new();
+    let mut text = String::new();
+    let mut kind = AtomTextKind::Text;
+
+    let mut feed_depth: Option<usize> = None;
+    let mut entry: Option<(AtomEntry, usize)> = None;
+    let mut person: Option<AtomPerson> = None;
+
+    loop {
+        let offset = reader.buffer_position();
+        let (ns, event) = reader
+            .read_resolved_event()
+            .map_err(|e| RssParseError::Xml(e.to_string()))?;
+
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let is_empty = matches!(event, Event::Empty(_));
+                let tag = classify(&ns, e);
+                let depth = stack.len();
+
+                let parent_base = bases.last().cloned().flatten();
+                let base = match attribute(e, b"xml:base") {
+                    Some(b) => match &parent_base {
+                        Some(parent) => parent.join(b.trim()).ok(),
+                        None => Url::parse(b.trim()).ok(),
+                    },
+                    None => parent_base,
+                };
+
+                let in_entry = entry.as_ref().is_some_and(|(_, d)| depth == d + 1);
+                let in_feed = entry.is_none() && feed_depth.is_some_and(|d| depth == d + 1);
+
+                match tag {
+                    Tag::Feed if feed_depth.is_none() => feed_depth = Some(depth),
+                    Tag::Entry if entry.is_none() => {
+                        let started = AtomEntry {
+                            byte_offset: Some(offset),
+                            ..Default::default()
+                        };
+                        entry = Some((started, depth));
+                    }
+                    Tag::Link if in_entry || in_feed => {
+                        if let Some(link) = link_of(e, base.as_ref()) {
+                            match &mut entry {
+                                Some((current, _)) => current.links.push(link),
+                                None => feed.links.push(link),
+                            }
+                        }
+                    }
+                    Tag::Category if in_entry => {
+                        if let (Some(category), Some((current, _))) = (category_of(e), &mut entry) {
+                            current.categories.push(category);
+                        }
+                    }
+                    Tag::Author if in_entry => person = Some(AtomPerson::default()),
+                    Tag::Title | Tag::Summary | Tag::Content if in_entry || in_feed => {
+                        kind = text_kind(e);
+
+                        if kind == AtomTextKind::Xhtml && !is_empty {
+                            let raw = reader
+                                .read_text(e.name())
+                                .map_err(|err| RssParseError::Xml(err.to_string()))?;
+                            let value = AtomText {
+                                kind,
+                                value: unwrap_xhtml_div(&raw),
+                            };
+                            assign_text(&mut feed, &mut entry, tag, value);
+                            // read_text consumed the end tag
+                            continue;
+                        }
+                    }
+                    _ => {}
+                }
+
+                if !is_empty {
+                    stack.push(tag);
+                    bases.push(base);
+                    text.clear();
+                }
+            }
+            Event::Text(_) | Event::CData(_) | Event::GeneralRef(_) => {
+                if let Err(detail) = push_content(&mut text, &event) {
+                    feed.diagnostics.push(ParseDiagnostic {
+                        item_index: entry.as_ref().map(|_| feed.entries.len()),
+                        byte_offset: Some(offset),
+                        kind: DiagnosticKind::Decoding { detail },
+                    });
+                }
+            }
+            Event::End(_) => {
+                let tag = stack.pop().unwrap_or(Tag::Other);
+                bases.pop();
+                let depth = stack.len();
+
+                let in_entry = entry.as_ref().is_some_and(|(_, d)| depth == d + 1);
+                let in_feed = entry.is_none() && feed_depth.is_some_and(|d| depth == d + 1);
+
+                match tag {
+                    Tag::Entry if entry.as_ref().is_some_and(|(_, d)| depth == *d) => {
+                        if let Some((finished, _)) = entry.take() {
+                            feed.entries.push(finished);
+                        }
+                    }
+                    Tag::Name | Tag::Email | Tag::Uri if person.is_some() => {
+                        let value = take_text(&mut text);
+                        if let Some(p) = &mut person {
+                            match tag {
+                                Tag::Name => p.name = value.unwrap_or_default(),
+                                Tag::Email => p.email = value,
+                                _ => p.uri = value,
+                            }
+                        }
+                    }
+                    Tag::Author if in_entry => {
+                        if let (Some(p), Some((current, _))) = (person.take(), &mut entry)
+                            && !p.name.is_empty()
+                        {
+                            current.authors.push(p);
+                        }
+                    }
+                    Tag::Title | Tag::Summary | Tag::Content if in_entry || in_feed => {
+                        if let Some(value) = take_text(&mut text) {
+                            assign_text(&mut feed, &mut entry, tag, AtomText { kind, value });
+                        }
+                    }
+                    Tag::Id | Tag::Updated | Tag::Published if in_entry || in_feed => {
+                        let value = take_text(&mut text);
+                        match (&mut entry, tag) {
+                            (Some((current, _)), Tag::Id) => current.id = value,
+                            (Some((current, _)), Tag::Updated) => current.updated = value,
+                            (Some((current, _)), _) => current.published = value,
+                            (None, Tag::Id) => feed.id = value,
+                            (None, Tag::Updated) => feed.updated = value,
+                            (None, _) => {}
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(feed)
+}
+
+fn assign_text(
+    feed: &mut AtomFeed,
+    entry: &mut Option<(AtomEntry, usize)>,
+    tag: Tag,
+    value: AtomText,
+) {
+    match (entry, tag) {
+        (Some((current, _)), Tag::Title) => current.title = Some(value),
+        (Some((current, _)), Tag::Summary) => current.summary = Some(value),
+        (Some((current, _)), Tag::Content) => current.content = Some(value),
+        (None, Tag::Title) => feed.title = Some(value),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xml:base="https://example.org/blog/">
+  <title type="text">Central Bank Blog</title>
+  <id>urn:uuid:60a76c80-d399-11d9-b93C-0003939e0af6</id>
+  <updated>2025-11-16T18:30:02Z</updated>
+  <link rel="self" href="feed.atom" />
+  <entry>
+    <title type="html">Rates &amp;amp; Liquidity</title>
+    <link rel="edit" href="/api/posts/1" />
+    <link rel="alternate" type="text/html" href="posts/rates" />
+    <link rel="enclosure" type="audio/mpeg" href="https://cdn.example.org/rates.mp3" />
+    <id>tag:example.org,2025:1</id>
+    <published>2025-11-16T09:30:00-05:00</published>
+    <updated>2025-11-16T10:00:00Z</updated>
+    <author><name>Jane Doe</name><email>jane@example.org</email></author>
+    <category term="monetary-policy" scheme="https://example.org/tags" />
+    <content type="xhtml"><div xmlns="http://www.w3.org/1999/xhtml"><p>Policy <b>unchanged</b></p></div></content>
+  </entry>
+  <entry xml:base="https://mirror.example.net/">
+    <title>Second</title>
+    <link href="second" />
+    <id>tag:example.org,2025:2</id>
+    <updated>2025-11-17T00:00:00+09:00</updated>
+    <summary>Short summary</summary>
+  </entry>
+</feed>"#;
+
+    #[test]
+    fn test_parse_feed() {
+        let feed = parse_feed(SAMPLE).expect("Failed to parse Atom");
+        assert_eq!(
+            feed.title.as_ref().map(|t| t.value.as_str()),
+            Some("Central Bank Blog")
+        );
+        assert_eq!(feed.links[0].href, "https://example.org/blog/feed.atom");
+        assert_eq!(feed.entries.len(), 2);
+
+        let first = &feed.entries[0];
+        assert_eq!(first.links.len(), 3);
+        assert_eq!(
+            first.alternate_link().map(|l| l.href.as_str()),
+            Some("https://example.org/blog/posts/rates")
+        );
+        assert_eq!(first.title.as_ref().unwrap().kind, AtomTextKind::Html);
+        assert_eq!(first.title.as_ref().unwrap().value, "Rates &amp; Liquidity");
+        assert_eq!(first.authors[0].name, "Jane Doe");
+        assert_eq!(first.authors[0].email.as_deref(), Some("jane@example.org"));
+        assert_eq!(first.categories[0].term, "monetary-policy");
+
+        let content = first.content.as_ref().unwrap();
+        assert_eq!(content.kind, AtomTextKind::Xhtml);
+        assert_eq!(content.value, "<p>Policy <b>unchanged</b></p>");
+
+        // Entry-level xml:base overrides the feed base
+        assert_eq!(
+            feed.entries[1].alternate_link().map(|l| l.href.as_str()),
+            Some("https://mirror.example.net/second")
+        );
+    }
+
+    #[test]
+    fn test_atom_parser_maps_entries() {
+        let items = AtomParser::new("cb_blog")
+            .parse(SAMPLE)
+            .expect("Failed to parse Atom");
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].source, "cb_blog");
+        assert_eq!(items[0].link, "https://example.org/blog/posts/rates");
+        // <published> wins over <updated>, RFC 3339 offsets are honoured
+        assert_eq!(
+            items[0].published_at.to_rfc3339(),
+            "2025-11-16T14:30:00+00:00"
+        );
+        assert_eq!(
+            items[0].summary.as_deref(),
+            Some("<p>Policy <b>unchanged</b></p>")
+        );
+        assert_eq!(
+            items[1].published_at.to_rfc3339(),
+            "2025-11-16T15:00:00+00:00"
+        );
+        assert_eq!(items[1].summary.as_deref(), Some("Short summary"));
+    }
+}
@@ -1,10 +1,6 @@
-use crate::error::RssParseError;
-use crate::rss::strip_cdata;
-use crate::rss::{RssItem, RssParser, RssResult};
+use crate::rss::generic::{collect_items, parse_channel};
+use crate::rss::{ParseOutcome, RssCategory, RssItem, RssParser, RssResult};
 use chrono::{DateTime, Utc};
-use quick_xml::Reader;
-use quick_xml::events::Event;
-use quick_xml::name::QName;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,9 +8,10 @@ pub struct BloombergRssItem {
     pub title: String,
     pub link: String,
     pub summary_html: Option<String>,
-    pub published_at: DateTime<Utc>,
-    pub creator: Option<String>,
-    pub categories: Vec<String>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub creators: Vec<String>,
+    pub categories: Vec<RssCategory>,
+    pub guid: Option<String>,
 }
 
 impl BloombergRssItem {
@@ -24,126 +21,38 @@ impl BloombergRssItem {
             self.title,
             self.link,
             self.summary_html,
-            Some(self.published_at),
+            self.published_at,
         )
+        .with_guid(self.guid)
+        .with_authors(self.creators)
+        .with_categories(self.categories)
     }
 }
 
 pub struct BloombergRssParser;
 
 impl RssParser for BloombergRssParser {
-    fn parse(&self, xml: &str) -> RssResult<Vec<RssItem>> {
-        let mut reader = Reader::from_str(xml);
-
-        let mut buf = Vec::new();
-        let mut items = Vec::new();
-        let mut in_item = false;
-
-        // Fields for each <item>
-        let mut title: Option<String> = None;
-        let mut link: Option<String> = None;
-        let mut description: Option<String> = None;
-        let mut creator: Option<String> = None;
-        let mut published_at: Option<String> = None;
-        let mut categories: Vec<String> = Vec::new();
-
-        loop {
-            match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(ref e)) => match e.name().as_ref() {
-                    b"item" => {
-                        in_item = true;
-                        title = None;
-                        link = None;
-                        description = None;
-                        creator = None;
-                        published_at = None;
-                        categories.clear();
-                    }
-                    b"title" => {
-                        if in_item {
-                            title = reader
-                                .read_text(QName(b"title"))
-                                .ok()
-                                .map(|t| strip_cdata(&t));
-                        }
-                    }
-                    b"link" => {
-                        if in_item {
-                            link = reader
-                                .read_text(QName(b"link"))
-                                .ok()
-                                .map(|t| t.into_owned());
-                        }
-                    }
-                    b"description" => {
-                        if in_item {
-                            description = reader
-                                .read_text(QName(b"description"))
-                                .ok()
-                                .map(|t| strip_cdata(&t));
-                        }
-                    }
-                    b"creator" => {
-                        if in_item {
-                            creator = reader
-                                .read_text(QName(b"creator"))
-                                .ok()
-                                .map(|t| t.into_owned());
-                        }
-                    }
-                    b"pubDate" => {
-                        if in_item {
-                            published_at = reader
-                                .read_text(QName(b"pubDate"))
-                                .ok()
-                                .map(|t| t.into_owned());
-                        }
-                    }
-                    b"category" => {
-                        if in_item {
-                            if let Ok(text) = reader.read_text(QName(b"category")) {
-                                categories.push(text.into_owned());
-                            }
-                        }
-                    }
-                    _ => {}
-                },
-                Ok(Event::End(ref e)) => {
-                    if e.name().as_ref() == b"item" && in_item {
-                        in_item = false;
-
-                        // Build BloombergRssItem
-                        if let (Some(title), Some(link), Some(published_at_str)) =
-                            (title.clone(), link.clone(), published_at.clone())
-                        {
-                            // Parse date safely
-                            let published_dt = DateTime::parse_from_rfc2822(&published_at_str)
-                                .map(|dt| dt.with_timezone(&Utc))
-                                .unwrap_or_else(|_| Utc::now());
-
-                            let bloomberg_item = BloombergRssItem {
-                                title,
-                                link,
-                                summary_html: description.clone(),
-                                published_at: published_dt,
-                                creator: creator.clone(),
-                                categories: categories.clone(),
-                            };
-
-                            // Convert to generic RssItem
-                            items.push(bloomberg_item.into_rss_item());
-                        }
-                    }
-                }
-
-                Ok(Event::Eof) => break,
-                Err(e) => return Err(RssParseError::Xml(e.to_string())),
-                _ => {}
-            }
-        }
-        buf.clear();
-
-        Ok(items)
+    fn parse_outcome(&self, xml: &str) -> RssResult<ParseOutcome> {
+        let channel = parse_channel(xml)?;
+
+        Ok(collect_items(channel, |item, title, link, published_at| {
+            let bloomberg_item = BloombergRssItem {
+                title,
+                link,
+                creators: item.authors(),
+                summary_html: item.description,
+                published_at,
+                categories: item
+                    .categories
+                    .into_iter()
+                    .map(|c| RssCategory::new(c.name, c.domain))
+                    .collect(),
+                guid: item.guid.map(|g| g.value),
+            };
+
+            // Convert to generic RssItem
+            bloomberg_item.into_rss_item()
+        }))
     }
 }
 
@@ -159,6 +68,30 @@ mod tests {
             .expect("Failed to read response body")
     }
 
+    #[test]
+    fn test_bloomberg_fixture_keeps_creators_and_category_domains() {
+        let xml = include_str!("../../tests/data/bloomberg_test.xml");
+        let items = BloombergRssParser.parse(xml).expect("Failed to parse XML");
+        assert_eq!(items.len(), 30);
+
+        assert_eq!(items[0].authors, vec!["Christian Dass"]);
+        assert_eq!(
+            items[11].categories,
+            vec![
+                RssCategory::new("NYS:JPM", Some("stock-symbol".to_string())),
+                RssCategory::new("NYS:ORCL", Some("stock-symbol".to_string())),
+            ]
+        );
+
+        let xml = r#"<rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/"><channel>
+<item><title>Joint byline</title><link>https://www.bloomberg.com/news/articles/a</link>
+<pubDate>Sun, 16 Nov 2025 14:00:00 GMT</pubDate>
+<dc:creator>Jane Doe</dc:creator><dc:creator>John Roe</dc:creator></item>
+</channel></rss>"#;
+        let items = BloombergRssParser.parse(xml).expect("Failed to parse XML");
+        assert_eq!(items[0].authors, vec!["Jane Doe", "John Roe"]);
+    }
+
     #[test]
     fn test_bloomberg_rss_wealth() {
         // https://feeds.bloomberg.com/wealth/news.rss
@@ -0,0 +1,353 @@
+use crate::error::RssParseError;
+use crate::rss::{RssItem, RssResult};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+// Lenient publish-date parsing shared by every parser
+
+/// Formats carrying a numeric offset (`%z` accepts `+0900` and `+09:00`)
+const ZONED_FORMATS: &[&str] = &[
+    "%d %b %Y %H:%M:%S %z",
+    "%d %b %Y %H:%M %z",
+    "%b %d, %Y %H:%M:%S %z",
+    "%b %d, %Y %I:%M %p %z",
+    "%Y-%m-%dT%H:%M:%S%.f%z",
+    "%Y-%m-%d %H:%M:%S%.f%z",
+    "%Y-%m-%d %H:%M:%S%.f %z",
+    "%Y-%m-%dT%H:%M%z",
+    "%Y-%m-%d %H:%M %z",
+];
+
+/// Formats without any zone; interpreted as UTC
+const NAIVE_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M",
+    "%Y/%m/%d %H:%M:%S",
+    "%d %b %Y %H:%M:%S",
+    "%d %b %Y %H:%M",
+    "%b %d, %Y %H:%M:%S",
+    "%b %d, %Y %I:%M %p",
+];
+
+/// Date-only formats; interpreted as midnight UTC
+const DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%Y/%m/%d", "%d %b %Y", "%b %d, %Y"];
+
+const WEEKDAYS: &[&str] = &[
+    "mon",
+    "tue",
+    "wed",
+    "thu",
+    "fri",
+    "sat",
+    "sun",
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+const MONTHS: &[&str] = &[
+    "january",
+    "february",
+    "march",
+    "april",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+/// Parse a feed timestamp: RFC 2822 (and its common mistakes), RFC 3339,
+/// ISO-like variants, zone abbreviations (`KST`, `CEST`, ...) and IANA
+/// zone names (`America/New_York`). Returns `None` instead of guessing.
+pub fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    if value.is_empty() {
+        return None;
+    }
+
+    if let Ok(dt) = DateTime::parse_from_rfc2822(&value) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc3339(&value) {
+        return Some(dt.with_timezone(&Utc));
+    }
+
+    let value = normalize_names(&value);
+    if let Ok(dt) = DateTime::parse_from_rfc2822(&value) {
+        return Some(dt.with_timezone(&Utc));
+    }
+
+    // A trailing IANA zone name is resolved against the local wall time.
+    // Only `Area/Location` names: chrono-tz also knows `CET`, `EET` and `MET`
+    // as zones with DST, while feeds mean the fixed offsets.
+    if let Some((local, zone)) = value.rsplit_once(' ')
+        && zone.contains('/')
+        && let Ok(tz) = zone.parse::<Tz>()
+    {
+        return parse_naive(local).and_then(|naive| {
+            tz.from_local_datetime(&naive)
+                .earliest()
+                .map(|dt| dt.with_timezone(&Utc))
+        });
+    }
+
+    let value = normalize_zone(&value);
+    ZONED_FORMATS
+        .iter()
+        .find_map(|fmt| DateTime::parse_from_str(&value, fmt).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+        .or_else(|| parse_naive(&value).map(|naive| naive.and_utc()))
+}
+
+fn parse_naive(value: &str) -> Option<NaiveDateTime> {
+    NAIVE_FORMATS
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(value, fmt).ok())
+        .or_else(|| {
+            DATE_FORMATS
+                .iter()
+                .find_map(|fmt| NaiveDate::parse_from_str(value, fmt).ok())
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+}
+
+/// Drop a leading `Sun,` / `Sunday` that may not match the actual date
+/// and shorten full month names to the `%b` form
+fn normalize_names(value: &str) -> String {
+    let value = match value.split_once(' ') {
+        Some((head, rest))
+            if WEEKDAYS.contains(&head.trim_end_matches(',').to_ascii_lowercase().as_str()) =>
+        {
+            rest
+        }
+        _ => value,
+    };
+
+    value
+        .split(' ')
+        .map(|token| {
+            let lower = token.to_ascii_lowercase();
+            match MONTHS
+                .iter()
+                .find(|month| lower.trim_end_matches(',') == **month)
+            {
+                Some(month) => format!("{}{}", &token[..3], &token[month.len()..]),
+                None => token.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Replace a trailing zone abbreviation or `Z` with a numeric offset
+fn normalize_zone(value: &str) -> String {
+    if let Some((rest, zone)) = value.rsplit_once(' ')
+        && let Some(offset) = zone_offset(zone)
+    {
+        return format!("{rest} {}", format_offset(offset));
+    }
+    if let Some(rest) = value.strip_suffix('Z')
+        && rest.ends_with(|c: char| c.is_ascii_digit())
+    {
+        return format!("{rest}+0000");
+    }
+    value.to_string()
+}
+
+fn format_offset(offset: FixedOffset) -> String {
+    let secs = offset.local_minus_utc();
+    let sign = if secs < 0 { '-' } else { '+' };
+    let secs = secs.abs();
+    format!("{sign}{:02}{:02}", secs / 3600, (secs % 3600) / 60)
+}
+
+/// Offsets for zone abbreviations seen in feeds; ambiguous ones (`CST`,
+/// `IST`) follow the RFC 822 / most common reading
+fn zone_offset(zone: &str) -> Option<FixedOffset> {
+    let minutes = match zone.to_ascii_uppercase().as_str() {
+        "UT" | "UTC" | "GMT" | "Z" | "WET" => 0,
+        "EST" => -5 * 60,
+        "EDT" => -4 * 60,
+        "CST" => -6 * 60,
+        "CDT" => -5 * 60,
+        "MST" => -7 * 60,
+        "MDT" => -6 * 60,
+        "PST" => -8 * 60,
+        "PDT" => -7 * 60,
+        "AKST" => -9 * 60,
+        "AKDT" => -8 * 60,
+        "HST" => -10 * 60,
+        "BST" | "CET" | "MET" | "WEST" => 60,
+        "CEST" | "EET" => 2 * 60,
+        "EEST" | "MSK" => 3 * 60,
+        "IST" => 5 * 60 + 30,
+        "HKT" | "SGT" | "AWST" => 8 * 60,
+        "JST" | "KST" => 9 * 60,
+        "AEST" => 10 * 60,
+        "AEDT" => 11 * 60,
+        "NZST" => 12 * 60,
+        "NZDT" => 13 * 60,
+        _ => return None,
+    };
+    FixedOffset::east_opt(minutes * 60)
+}
+
+/// What to do with an item whose publish date is missing or unparseable
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum DateFallback {
+    /// Drop the item
+    Skip,
+    /// Keep it, dated by when it was first seen; the stored row keeps the
+    /// original first-seen time on later polls
+    #[default]
+    FirstSeen,
+    /// Fail the whole feed with `RssParseError::InvalidDate`
+    Error,
+}
+
+impl DateFallback {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Skip => "skip",
+            Self::FirstSeen => "first_seen",
+            Self::Error => "error",
+        }
+    }
+
+    /// Apply the policy to parsed items flagged with `published_at_fallback`
+    pub fn apply(&self, items: Vec<RssItem>) -> RssResult<Vec<RssItem>> {
+        match self {
+            Self::FirstSeen => Ok(items),
+            Self::Skip => Ok(items
+                .into_iter()
+                .filter(|item| !item.published_at_fallback)
+                .collect()),
+            Self::Error => match items.iter().find(|item| item.published_at_fallback) {
+                Some(item) => Err(RssParseError::InvalidDate(format!(
+                    "no usable publish date for '{}'",
+                    item.link
+                ))),
+                None => Ok(items),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rfc3339(value: &str) -> Option<String> {
+        parse_date(value).map(|dt| dt.to_rfc3339())
+    }
+
+    #[test]
+    fn test_parse_date_variants() {
+        let cases = [
+            // RFC 2822 and RFC 3339
+            (
+                "Sun, 16 Nov 2025 09:00:00 +0000",
+                "2025-11-16T09:00:00+00:00",
+            ),
+            ("Sun, 16 Nov 2025 09:00:00 GMT", "2025-11-16T09:00:00+00:00"),
+            ("Sun, 16 Nov 2025 04:00:00 EST", "2025-11-16T09:00:00+00:00"),
+            ("2025-11-16T18:00:00+09:00", "2025-11-16T09:00:00+00:00"),
+            ("2025-11-16T09:00:00.123Z", "2025-11-16T09:00:00.123+00:00"),
+            // Wrong or long weekday, missing seconds, single-digit day
+            (
+                "Mon, 16 Nov 2025 09:00:00 +0000",
+                "2025-11-16T09:00:00+00:00",
+            ),
+            (
+                "Sunday, 16 November 2025 09:00 +0000",
+                "2025-11-16T09:00:00+00:00",
+            ),
+            ("Tue, 4 Nov 2025 09:00 GMT", "2025-11-04T09:00:00+00:00"),
+            // Named zones
+            ("Sun, 16 Nov 2025 18:00:00 KST", "2025-11-16T09:00:00+00:00"),
+            ("16 Nov 2025 10:00:00 CET", "2025-11-16T09:00:00+00:00"),
+            // Abbreviations are fixed offsets, also in summer
+            ("16 Jul 2025 10:00:00 CET", "2025-07-16T09:00:00+00:00"),
+            ("16 Jul 2025 11:00:00 EET", "2025-07-16T09:00:00+00:00"),
+            ("16 Jul 2025 10:00:00 MET", "2025-07-16T09:00:00+00:00"),
+            (
+                "2025-11-16 04:00:00 America/New_York",
+                "2025-11-16T09:00:00+00:00",
+            ),
+            (
+                "2025-07-16 05:00:00 America/New_York",
+                "2025-07-16T09:00:00+00:00",
+            ),
+            // ISO-ish and naive values
+            ("2025-11-16 18:00:00+0900", "2025-11-16T09:00:00+00:00"),
+            ("2025-11-16 09:00:00Z", "2025-11-16T09:00:00+00:00"),
+            ("2025-11-16T09:00:00", "2025-11-16T09:00:00+00:00"),
+            ("2025-11-16 09:00", "2025-11-16T09:00:00+00:00"),
+            ("Nov 16, 2025 9:00 AM +0000", "2025-11-16T09:00:00+00:00"),
+            ("2025-11-16", "2025-11-16T00:00:00+00:00"),
+            ("  16 Nov 2025  ", "2025-11-16T00:00:00+00:00"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(rfc3339(input).as_deref(), Some(expected), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_parse_date_rejects_garbage() {
+        for input in ["", "   ", "yesterday", "16/11", "2025-13-45", "Sun, 99 Foo"] {
+            assert_eq!(parse_date(input), None, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_date_fallback_policy() {
+        let dated = RssItem::new(
+            "t",
+            "dated",
+            "https://e.com/1",
+            None,
+            parse_date("2025-11-16"),
+        );
+        let undated = RssItem::new("t", "undated", "https://e.com/2", None, None);
+        assert!(!dated.published_at_fallback);
+        assert!(undated.published_at_fallback);
+
+        let items = || {
+            vec![
+                RssItem::new(
+                    "t",
+                    "dated",
+                    "https://e.com/1",
+                    None,
+                    parse_date("2025-11-16"),
+                ),
+                RssItem::new("t", "undated", "https://e.com/2", None, None),
+            ]
+        };
+
+        assert_eq!(DateFallback::FirstSeen.apply(items()).unwrap().len(), 2);
+
+        let kept = DateFallback::Skip.apply(items()).unwrap();
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].title, "dated");
+
+        assert!(matches!(
+            DateFallback::Error.apply(items()),
+            Err(RssParseError::InvalidDate(_))
+        ));
+    }
+}
@@ -0,0 +1,197 @@
+use crate::rss::{DiagnosticKind, ParseDiagnostic};
+use encoding_rs::{Encoding, UTF_8, UTF_16BE, UTF_16LE};
+use serde::Serialize;
+use std::fmt;
+
+/// Where the encoding of a payload was learned from, strongest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EncodingSource {
+    /// A UTF-8 / UTF-16 byte order mark
+    Bom,
+    /// The `charset` parameter of the HTTP `Content-Type` header
+    Charset,
+    /// The `encoding` of the XML declaration
+    Declaration,
+    /// Nothing said otherwise; UTF-8 (also the only JSON Feed encoding)
+    Default,
+}
+
+impl EncodingSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Bom => "bom",
+            Self::Charset => "charset",
+            Self::Declaration => "declaration",
+            Self::Default => "default",
+        }
+    }
+}
+
+impl fmt::Display for EncodingSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A response body decoded to UTF-8
+#[derive(Debug, Clone)]
+pub struct DecodedPayload {
+    pub text: String,
+    pub encoding: &'static Encoding,
+    pub source: EncodingSource,
+    /// Some byte sequences were invalid and replaced with U+FFFD
+    pub malformed: bool,
+}
+
+impl DecodedPayload {
+    /// WHATWG name of the encoding, e.g. `Shift_JIS`. Latin-1 labels map to
+    /// `windows-1252`, its superset.
+    pub fn encoding_name(&self) -> &'static str {
+        self.encoding.name()
+    }
+
+    /// Feed-level warning for a body that didn't decode cleanly
+    pub fn diagnostic(&self) -> Option<ParseDiagnostic> {
+        self.malformed.then(|| ParseDiagnostic {
+            item_index: None,
+            byte_offset: None,
+            kind: DiagnosticKind::Decoding {
+                detail: format!(
+                    "invalid {} byte sequences replaced (encoding from {})",
+                    self.encoding_name(),
+                    self.source
+                ),
+            },
+        })
+    }
+}
+
+/// Decode a feed body to UTF-8. A byte order mark wins, then the HTTP
+/// `charset` (RFC 7303), then the XML declaration; UTF-8 otherwise.
+pub fn decode_payload(body: &[u8], charset: Option<&str>) -> DecodedPayload {
+    let (encoding, source) = if let Some((encoding, _)) = Encoding::for_bom(body) {
+        (encoding, EncodingSource::Bom)
+    } else if let Some(encoding) = charset.and_then(|c| Encoding::for_label(c.as_bytes())) {
+        (encoding, EncodingSource::Charset)
+    } else if let Some(encoding) = declared_encoding(body) {
+        (encoding, EncodingSource::Declaration)
+    } else {
+        (UTF_8, EncodingSource::Default)
+    };
+
+    // `decode` strips the BOM
+    let (text, _, malformed) = encoding.decode(body);
+    DecodedPayload {
+        text: text.into_owned(),
+        encoding,
+        source,
+        malformed,
+    }
+}
+
+/// The `charset` parameter of a `Content-Type` header value
+pub fn charset_of(content_type: &str) -> Option<&str> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("charset")
+            .then(|| value.trim().trim_matches('"'))
+            .filter(|value| !value.is_empty())
+    })
+}
+
+/// `encoding="…"` of a leading `<?xml …?>` declaration. A declaration that is
+/// readable as ASCII can't really be UTF-16, so such a claim is ignored.
+fn declared_encoding(body: &[u8]) -> Option<&'static Encoding> {
+    let start = body.iter().position(|b| !b.is_ascii_whitespace())?;
+    let body = &body[start..];
+    if !body.starts_with(b"<?xml") {
+        return None;
+    }
+    let end = body.windows(2).take(1024).position(|w| w == b"?>")?;
+    let declaration = std::str::from_utf8(&body[..end]).ok()?;
+
+    let rest = &declaration[declaration.find("encoding")? + "encoding".len()..];
+    let rest = rest.trim_start().strip_prefix('=')?.trim_start();
+    let quote = rest.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+    let label = rest[1..].split(quote).next()?;
+
+    Encoding::for_label(label.as_bytes()).filter(|e| *e != UTF_16LE && *e != UTF_16BE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rss::RssParser;
+    use crate::rss::generic::GenericRssParser;
+
+    fn first_item(payload: &DecodedPayload) -> (String, String) {
+        let items = GenericRssParser::new("fixture")
+            .parse(&payload.text)
+            .expect("Failed to parse XML");
+        (items[0].title.clone(), items[0].summary.clone().unwrap())
+    }
+
+    #[test]
+    fn test_decode_declared_encodings() {
+        let latin1 = decode_payload(
+            include_bytes!("../../tests/data/encoding/iso-8859-1.xml"),
+            None,
+        );
+        assert_eq!(latin1.source, EncodingSource::Declaration);
+        assert_eq!(latin1.encoding_name(), "windows-1252");
+        assert_eq!(
+            first_item(&latin1),
+            (
+                "Börse Frankfurt: Zinsentscheid der EZB".to_string(),
+                "Café, crème brûlée, 10 °C".to_string()
+            )
+        );
+
+        let cp1252 = decode_payload(
+            include_bytes!("../../tests/data/encoding/windows-1252.xml"),
+            None,
+        );
+        assert_eq!(first_item(&cp1252).0, "“Euro” falls – €1.05");
+
+        let sjis = decode_payload(
+            include_bytes!("../../tests/data/encoding/shift_jis.xml"),
+            None,
+        );
+        assert_eq!(sjis.encoding_name(), "Shift_JIS");
+        assert_eq!(first_item(&sjis).0, "金融庁、新たな監督指針を公表");
+        assert!(!sjis.malformed);
+    }
+
+    #[test]
+    fn test_decode_charset_and_bom() {
+        let body = include_bytes!("../../tests/data/encoding/euc-kr.xml");
+        let euc_kr = decode_payload(body, charset_of("application/rss+xml; charset=\"EUC-KR\""));
+        assert_eq!(euc_kr.source, EncodingSource::Charset);
+        assert_eq!(first_item(&euc_kr).0, "한국거래소 공시");
+
+        // Without the header the body is not valid UTF-8
+        let guessed = decode_payload(body, None);
+        assert_eq!(guessed.source, EncodingSource::Default);
+        assert!(guessed.malformed);
+        assert!(guessed.diagnostic().is_some());
+
+        // The BOM overrides both the header and the declaration
+        let utf16 = decode_payload(
+            include_bytes!("../../tests/data/encoding/utf-16le-bom.xml"),
+            Some("iso-8859-1"),
+        );
+        assert_eq!(utf16.source, EncodingSource::Bom);
+        assert_eq!(utf16.encoding_name(), "UTF-16LE");
+        assert_eq!(first_item(&utf16).0, "Zürich — Ελληνικά — 日本語");
+    }
+
+    #[test]
+    fn test_charset_of() {
+        assert_eq!(charset_of("text/xml; charset=Shift_JIS"), Some("Shift_JIS"));
+        assert_eq!(charset_of("text/xml;CHARSET=\"utf-8\""), Some("utf-8"));
+        assert_eq!(charset_of("application/rss+xml"), None);
+        assert_eq!(charset_of("text/xml; charset="), None);
+    }
+}
@@ -0,0 +1,604 @@
+use crate::error::RssParseError;
+use crate::rss::date::parse_date;
+use crate::rss::xml::{
+    CONTENT_NS, DC_NS, RSS1_NS, attribute, namespace_of, push_content, take_text,
+};
+use crate::rss::{
+    DiagnosticKind, ParseDiagnostic, ParseOutcome, RssCategory, RssItem, RssParser, RssResult,
+};
+use chrono::{DateTime, Utc};
+use quick_xml::NsReader;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::ResolveResult;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Rss2Channel {
+    pub title: Option<String>,
+    pub link: Option<String>,
+    pub description: Option<String>,
+    pub items: Vec<Rss2Item>,
+    /// Text problems found while reading (see `push_content`)
+    pub diagnostics: Vec<ParseDiagnostic>,
+}
+
+/// Every `<item>` element defined by RSS 2.0 plus the common
+/// `dc:creator` and `content:encoded` extensions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Rss2Item {
+    pub title: Option<String>,
+    pub link: Option<String>,
+    pub description: Option<String>,
+    pub author: Option<String>,
+    pub creators: Vec<String>,
+    pub categories: Vec<Rss2Category>,
+    pub comments: Option<String>,
+    pub enclosures: Vec<Rss2Enclosure>,
+    pub guid: Option<Rss2Guid>,
+    pub pub_date: Option<String>,
+    /// `dc:date` (ISO 8601), the RSS 1.0 publish date
+    pub dc_date: Option<String>,
+    pub source: Option<Rss2Source>,
+    pub content_encoded: Option<String>,
+    /// Byte offset of the `<item>` start tag in the payload
+    pub byte_offset: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rss2Category {
+    pub name: String,
+    pub domain: Option<String>,
+}
+
+impl From<Rss2Category> for RssCategory {
+    fn from(category: Rss2Category) -> Self {
+        RssCategory::new(category.name, category.domain)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rss2Enclosure {
+    pub url: String,
+    pub length: Option<u64>,
+    pub mime_type: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rss2Guid {
+    pub value: String,
+    /// Defaults to `true` when the attribute is absent (RSS 2.0 spec)
+    pub is_permalink: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rss2Source {
+    pub url: Option<String>,
+    pub name: String,
+}
+
+impl Rss2Item {
+    /// Fields every stored item needs: title, link and a publish date
+    /// element; the date is `None` when its value can't be parsed. The error
+    /// names the first missing element.
+    pub fn required(&self) -> Result<(String, String, Option<DateTime<Utc>>), &'static str> {
+        let title = self.title.clone().ok_or("title")?;
+        let link = self.link.clone().ok_or("link")?;
+        let date = self.date().ok_or("pubDate")?;
+        Ok((title, link, parse_date(date)))
+    }
+
+    /// Raw `pubDate`, falling back to `dc:date`
+    pub fn date(&self) -> Option<&str> {
+        self.pub_date.as_deref().or(self.dc_date.as_deref())
+    }
+
+    /// `dc:creator` values, falling back to the RSS `<author>` element
+    pub fn authors(&self) -> Vec<String> {
+        if self.creators.is_empty() {
+            self.author.iter().cloned().collect()
+        } else {
+            self.creators.clone()
+        }
+    }
+
+    pub fn into_rss_item(self, source: &str) -> Option<RssItem> {
+        let (title, link, published_at) = self.required().ok()?;
+        Some(self.build(source, title, link, published_at))
+    }
+
+    fn build(
+        self,
+        source: &str,
+        title: String,
+        link: String,
+        published_at: Option<DateTime<Utc>>,
+    ) -> RssItem {
+        let authors = self.authors();
+        RssItem::new(source, title, link, self.description, published_at)
+            .with_guid(self.guid.map(|g| g.value))
+            .with_authors(authors)
+            .with_categories(self.categories.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Plain RSS 2.0 parser; `source` is stored on every produced item
+#[derive(Debug, Clone)]
+pub struct GenericRssParser {
+    source: String,
+}
+
+impl GenericRssParser {
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+        }
+    }
+}
+
+impl RssParser for GenericRssParser {
+    fn parse_outcome(&self, xml: &str) -> RssResult<ParseOutcome> {
+        Ok(collect_items(
+            parse_channel(xml)?,
+            |item, title, link, published_at| item.build(&self.source, title, link, published_at),
+        ))
+    }
+}
+
+/// Turn the channel's items into `RssItem`s with `convert`, which receives
+/// each item with its required fields. Items missing one are skipped and,
+/// like unparseable dates, reported in the outcome's diagnostics.
+pub fn collect_items(
+    channel: Rss2Channel,
+    mut convert: impl FnMut(Rss2Item, String, String, Option<DateTime<Utc>>) -> RssItem,
+) -> ParseOutcome {
+    let mut outcome = ParseOutcome::new(channel.diagnostics, channel.link);
+    for (index, item) in channel.items.into_iter().enumerate() {
+        let offset = item.byte_offset;
+        match item.required() {
+            Ok((title, link, published_at)) => {
+                let date = item.date().unwrap_or_default().to_string();
+                outcome.push(
+                    index,
+                    offset,
+                    convert(item, title, link, published_at),
+                    &date,
+                );
+            }
+            Err(field) => outcome.skip(index, offset, field),
+        }
+    }
+    outcome
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tag {
+    Channel,
+    Item,
+    Title,
+    Link,
+    Description,
+    Author,
+    Category,
+    Comments,
+    Enclosure,
+    Guid,
+    PubDate,
+    Source,
+    Creator,
+    DcDate,
+    ContentEncoded,
+    Other,
+}
+
+fn classify(ns: &ResolveResult, e: &BytesStart) -> Tag {
+    let name = e.name();
+    let local = name.local_name();
+    let prefix = name.prefix();
+
+    match namespace_of(ns, prefix.as_ref().map(|p| p.as_ref())) {
+        None | Some(RSS1_NS) => match local.as_ref() {
+            b"channel" => Tag::Channel,
+            b"item" => Tag::Item,
+            b"title" => Tag::Title,
+            b"link" => Tag::Link,
+            b"description" => Tag::Description,
+            b"author" => Tag::Author,
+            b"category" => Tag::Category,
+            b"comments" => Tag::Comments,
+            b"enclosure" => Tag::Enclosure,
+            b"guid" => Tag::Guid,
+            b"pubDate" => Tag::PubDate,
+            b"source" => Tag::Source,
+            _ => Tag::Other,
+        },
+        Some(DC_NS) if local.as_ref() == b"creator" => Tag::Creator,
+        Some(DC_NS) if local.as_ref() == b"date" => Tag::DcDate,
+        Some(CONTENT_NS) if local.as_ref() == b"encoded" => Tag::ContentEncoded,
+        _ => Tag::Other,
+    }
+}
+
+/// Attributes of the element currently being read, applied at its end tag
+#[derive(Default)]
+struct PendingAttrs {
+    domain: Option<String>,
+    is_permalink: Option<String>,
+    url: Option<String>,
+}
+
+/// Parse an RSS 2.0 (or RSS 1.0 / RDF) document into its channel metadata and items
+pub fn parse_channel(xml: &str) -> RssResult<Rss2Channel> {
+    let mut reader = NsReader::from_str(xml);
+
+    let mut buf = Vec::new();
+    let mut channel = Rss2Channel::default();
+    let mut stack: Vec<Tag> = Vec::new();
+    let mut text = String::new();
+    let mut pending = PendingAttrs::default();
+
+    let mut channel_depth: Option<usize> = None;
+    let mut item: Option<(Rss2Item, usize)> = None;
+
+    loop {
+        let offset = reader.buffer_position();
+        let (ns, event) = reader
+            .read_resolved_event_into(&mut buf)
+            .map_err(|e| RssParseError::Xml(e.to_string()))?;
+
+        match event {
+            Event::Start(ref e) => {
+                let tag = classify(&ns, e);
+                let depth = stack.len();
+
+                match (tag, &mut item) {
+                    (Tag::Channel, None) if channel_depth.is_none() => {
+                        channel_depth = Some(depth);
+                    }
+                    (Tag::Item, None) => {
+                        let started = Rss2Item {
+                            byte_offset: Some(offset),
+                            ..Default::default()
+                        };
+                        item = Some((started, depth));
+                    }
+                    (_, Some((current, item_depth))) if depth == *item_depth + 1 => {
+                        pending = read_attrs(tag, e);
+                        if tag == Tag::Enclosure {
+                            push_enclosure(current, e);
+                        }
+                    }
+                    _ => {}
+                }
+
+                stack.push(tag);
+                text.clear();
+            }
+            Event::Empty(ref e) => {
+                let tag = classify(&ns, e);
+                if let Some((current, item_depth)) = &mut item
+                    && stack.len() == *item_depth + 1
+                    && tag == Tag::Enclosure
+                {
+                    push_enclosure(current, e);
+                }
+            }
+            Event::Text(_) | Event::CData(_) | Event::GeneralRef(_) => {
+                if let Err(detail) = push_content(&mut text, &event) {
+                    channel.diagnostics.push(ParseDiagnostic {
+                        item_index: item.as_ref().map(|_| channel.items.len()),
+                        byte_offset: Some(offset),
+                        kind: DiagnosticKind::Decoding { detail },
+                    });
+                }
+            }
+            Event::End(_) => {
+                let tag = stack.pop().unwrap_or(Tag::Other);
+                let depth = stack.len();
+
+                match &mut item {
+                    Some((_, item_depth)) if tag == Tag::Item && depth == *item_depth => {
+                        if let Some((finished, _)) = item.take() {
+                            channel.items.push(finished);
+                        }
+                    }
+                    Some((current, item_depth)) if depth == *item_depth + 1 => {
+                        let attrs = std::mem::take(&mut pending);
+                        assign_item_field(current, tag, take_text(&mut text), attrs);
+                    }
+                    None if channel_depth.is_some_and(|d| depth == d + 1) => match tag {
+                        Tag::Title => channel.title = take_text(&mut text),
+                        Tag::Link => channel.link = take_text(&mut text),
+                        Tag::Description => channel.description = take_text(&mut text),
+                        _ => {}
+                    },
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(channel)
+}
+
+fn read_attrs(tag: Tag, e: &BytesStart) -> PendingAttrs {
+    match tag {
+        Tag::Category => PendingAttrs {
+            domain: attribute(e, b"domain"),
+            ..Default::default()
+        },
+        Tag::Guid => PendingAttrs {
+            is_permalink: attribute(e, b"isPermaLink"),
+            ..Default::default()
+        },
+        Tag::Source => PendingAttrs {
+            url: attribute(e, b"url"),
+            ..Default::default()
+        },
+        _ => PendingAttrs::default(),
+    }
+}
+
+fn push_enclosure(item: &mut Rss2Item, e: &BytesStart) {
+    if let Some(url) = attribute(e, b"url") {
+        item.enclosures.push(Rss2Enclosure {
+            url,
+            length: attribute(e, b"length").and_then(|l| l.trim().parse().ok()),
+            mime_type: attribute(e, b"type"),
+        });
+    }
+}
+
+fn assign_item_field(item: &mut Rss2Item, tag: Tag, value: Option<String>, attrs: PendingAttrs) {
+    let Some(value) = value else {
+        return;
+    };
+
+    match tag {
+        Tag::Title => item.title = Some(value),
+        Tag::Link => item.link = Some(value),
+        Tag::Description => item.description = Some(value),
+        Tag::Author => item.author = Some(value),
+        Tag::Creator => item.creators.push(value),
+        Tag::Category => item.categories.push(Rss2Category {
+            name: value,
+            domain: attrs.domain,
+        }),
+        Tag::Comments => item.comments = Some(value),
+        Tag::Guid => {
+            let is_permalink = attrs
+                .is_permalink
+                .map(|v| !v.trim().eq_ignore_ascii_case("false"))
+                .unwrap_or(true);
+            item.guid = Some(Rss2Guid {
+                value,
+                is_permalink,
+            });
+        }
+        Tag::PubDate => item.pub_date = Some(value),
+        Tag::DcDate => item.dc_date = Some(value),
+        Tag::Source => {
+            item.source = Some(Rss2Source {
+                url: attrs.url,
+                name: value,
+            })
+        }
+        Tag::ContentEncoded => item.content_encoded = Some(value),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:content="http://purl.org/rss/1.0/modules/content/" xmlns:atom="http://www.w3.org/2005/Atom">
+  <channel>
+    <title>Sample &amp; Co</title>
+    <link>https://example.com/</link>
+    <atom:link href="https://example.com/rss" rel="self" />
+    <description>Sample feed</description>
+    <item>
+      <title><![CDATA[Fed holds rates]]></title>
+      <link>https://example.com/fed</link>
+      <description>Rates &lt;b&gt;unchanged&lt;/b&gt; &#8212; again</description>
+      <author>desk@example.com (Desk)</author>
+      <dc:creator>Jane Doe</dc:creator>
+      <dc:creator>John Roe</dc:creator>
+      <category domain="topic">Economy</category>
+      <category>Rates</category>
+      <comments>https://example.com/fed#comments</comments>
+      <enclosure url="https://example.com/fed.mp3" length="1024" type="audio/mpeg" />
+      <guid isPermaLink="false">fed-2025-11-16</guid>
+      <pubDate>Sun, 16 Nov 2025 14:00:00 GMT</pubDate>
+      <source url="https://example.com/wire.xml">Wire</source>
+      <content:encoded><![CDATA[<p>Full text</p>]]></content:encoded>
+      <media:content xmlns:media="http://search.yahoo.com/mrss/" url="https://example.com/a.jpg">
+        <media:description>Not the item description</media:description>
+      </media:content>
+    </item>
+    <item>
+      <title>No date</title>
+      <link>https://example.com/no-date</link>
+    </item>
+  </channel>
+</rss>"#;
+
+    #[test]
+    fn test_parse_channel_spec_elements() {
+        let channel = parse_channel(SAMPLE).expect("Failed to parse XML");
+        assert_eq!(channel.title.as_deref(), Some("Sample & Co"));
+        assert_eq!(channel.link.as_deref(), Some("https://example.com/"));
+        assert_eq!(channel.items.len(), 2);
+
+        let item = &channel.items[0];
+        assert_eq!(item.title.as_deref(), Some("Fed holds rates"));
+        assert_eq!(item.link.as_deref(), Some("https://example.com/fed"));
+        assert_eq!(
+            item.description.as_deref(),
+            Some("Rates <b>unchanged</b> \u{2014} again")
+        );
+        assert_eq!(item.author.as_deref(), Some("desk@example.com (Desk)"));
+        assert_eq!(item.creators, vec!["Jane Doe", "John Roe"]);
+        assert_eq!(
+            item.categories,
+            vec![
+                Rss2Category {
+                    name: "Economy".to_string(),
+                    domain: Some("topic".to_string())
+                },
+                Rss2Category {
+                    name: "Rates".to_string(),
+                    domain: None
+                },
+            ]
+        );
+        assert_eq!(
+            item.comments.as_deref(),
+            Some("https://example.com/fed#comments")
+        );
+        assert_eq!(item.enclosures[0].length, Some(1024));
+        assert_eq!(
+            item.guid,
+            Some(Rss2Guid {
+                value: "fed-2025-11-16".to_string(),
+                is_permalink: false
+            })
+        );
+        assert_eq!(item.source.as_ref().map(|s| s.name.as_str()), Some("Wire"));
+        assert_eq!(item.content_encoded.as_deref(), Some("<p>Full text</p>"));
+    }
+
+    #[test]
+    fn test_generic_parser_requires_title_link_and_date() {
+        let items = GenericRssParser::new("sample")
+            .parse(SAMPLE)
+            .expect("Failed to parse XML");
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].source, "sample");
+        assert_eq!(items[0].title, "Fed holds rates");
+        assert_eq!(items[0].guid.as_deref(), Some("fed-2025-11-16"));
+        assert_eq!(items[0].authors, vec!["Jane Doe", "John Roe"]);
+        assert_eq!(
+            items[0].categories[0],
+            RssCategory::new("Economy", Some("topic".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_unparseable_pub_date_is_flagged() {
+        let xml = r#"<rss version="2.0"><channel><item>
+            <title>Undated</title>
+            <link>https://example.com/undated</link>
+            <pubDate>sometime last week</pubDate>
+        </item></channel></rss>"#;
+
+        let parser = GenericRssParser::new("sample");
+        let first = parser.parse(xml).expect("Failed to parse XML");
+        let second = parser.parse(xml).expect("Failed to parse XML");
+
+        assert!(first[0].published_at_fallback);
+        assert_eq!(first[0].id, second[0].id);
+    }
+
+    #[test]
+    fn test_parse_outcome_diagnostics() {
+        let xml = r#"<rss version="2.0"><channel>
+            <item><title>Kept</title><link>https://e.com/1</link><pubDate>Sun, 16 Nov 2025 14:00:00 GMT</pubDate></item>
+            <item><title>No link</title><pubDate>Sun, 16 Nov 2025 14:00:00 GMT</pubDate></item>
+            <item><title>Bad date</title><link>https://e.com/3</link><pubDate>soon</pubDate></item>
+            <item><title>Caf&eacute;</title><link>https://e.com/4</link><pubDate>Sun, 16 Nov 2025 14:00:00 GMT</pubDate></item>
+        </channel></rss>"#;
+        let item_offsets: Vec<u64> = xml.match_indices("<item>").map(|(i, _)| i as u64).collect();
+
+        let outcome = GenericRssParser::new("sample")
+            .parse_outcome(xml)
+            .expect("Failed to parse XML");
+        assert_eq!(outcome.items.len(), 3);
+        assert_eq!(outcome.items[2].title, "Caf&eacute;");
+        assert_eq!(outcome.skipped(), 1);
+
+        let mut diagnostics = outcome.diagnostics.clone();
+        diagnostics.sort_by_key(|d| d.item_index);
+        assert_eq!(
+            diagnostics[0],
+            ParseDiagnostic {
+                item_index: Some(1),
+                byte_offset: Some(item_offsets[1]),
+                kind: DiagnosticKind::MissingField {
+                    field: "link".to_string()
+                },
+            }
+        );
+        assert_eq!(
+            diagnostics[1].kind,
+            DiagnosticKind::InvalidDate {
+                value: "soon".to_string()
+            }
+        );
+        assert_eq!(diagnostics[2].item_index, Some(3));
+        assert!(matches!(
+            diagnostics[2].kind,
+            DiagnosticKind::Decoding { .. }
+        ));
+        assert!(diagnostics[2].byte_offset > Some(item_offsets[3]));
+
+        assert!(matches!(
+            outcome.into_strict(),
+            Err(RssParseError::Strict { count: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_rss1_rdf() {
+        let xml = r#"<?xml version="1.0"?>
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns="http://purl.org/rss/1.0/" xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <channel rdf:about="https://regulator.example.jp/rss">
+    <title>Regulator News</title>
+    <link>https://regulator.example.jp/</link>
+    <items><rdf:Seq><rdf:li rdf:resource="https://regulator.example.jp/n/1" /></rdf:Seq></items>
+  </channel>
+  <item rdf:about="https://regulator.example.jp/n/1">
+    <title>Notice 1</title>
+    <link>https://regulator.example.jp/n/1</link>
+    <dc:date>2025-11-16T09:00:00+09:00</dc:date>
+    <dc:creator>Press Office</dc:creator>
+  </item>
+</rdf:RDF>"#;
+
+        let items = GenericRssParser::new("regulator")
+            .parse(xml)
+            .expect("Failed to parse RDF");
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].title, "Notice 1");
+        assert_eq!(
+            items[0].published_at.to_rfc3339(),
+            "2025-11-16T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_parse_bloomberg_fixture() {
+        let xml = include_str!("../../tests/data/bloomberg_test.xml");
+        let channel = parse_channel(xml).expect("Failed to parse XML");
+
+        assert_eq!(channel.title.as_deref(), Some("Bloomberg Markets"));
+        assert!(!channel.items.is_empty());
+
+        let first = &channel.items[0];
+        assert_eq!(first.creators, vec!["Christian Dass"]);
+        assert_eq!(first.categories[0].domain.as_deref(), Some("stock-symbol"));
+        assert!(first.guid.as_ref().is_some_and(|g| g.is_permalink));
+        // <media:description> must not leak into the item description
+        assert!(
+            first
+                .description
+                .as_deref()
+                .is_some_and(|d| d.starts_with("Investors"))
+        );
+    }
+}
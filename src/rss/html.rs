@@ -0,0 +1,722 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::sync::LazyLock;
+
+// Summary normalization: feed descriptions are HTML fragments of varying
+// quality, so plain text comes from a tolerant tokenizer rather than a full
+// HTML parser. Sanitized HTML is left to ammonia, which parses like a browser.
+
+/// Default cap on plaintext summaries, in characters
+pub const SUMMARY_TEXT_MAX_CHARS: usize = 500;
+
+/// Tags kept by `sanitize_html`
+const ALLOWED_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "b",
+    "blockquote",
+    "br",
+    "caption",
+    "code",
+    "div",
+    "em",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "img",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "q",
+    "s",
+    "small",
+    "span",
+    "strong",
+    "sub",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "th",
+    "thead",
+    "tr",
+    "u",
+    "ul",
+];
+
+/// Elements dropped together with their content
+const DROPPED_TAGS: &[&str] = &[
+    "button", "embed", "form", "frameset", "head", "iframe", "math", "noscript", "object",
+    "script", "select", "style", "svg", "template", "textarea", "title",
+];
+
+/// Elements whose content is not markup
+const RAW_TEXT_TAGS: &[&str] = &["script", "style", "textarea", "title"];
+
+const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Tags that separate words when converted to plain text
+const BLOCK_TAGS: &[&str] = &[
+    "blockquote",
+    "br",
+    "caption",
+    "dd",
+    "div",
+    "dt",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "li",
+    "p",
+    "pre",
+    "td",
+    "th",
+    "tr",
+];
+
+/// Image and link URLs of analytics beacons and feed share buttons
+const TRACKER_PATTERNS: &[&str] = &[
+    "feeds.feedburner.com/~ff/",
+    "feeds.feedburner.com/~r/",
+    "feedsportal.com",
+    "doubleclick.net",
+    "google-analytics.com",
+    "googletagmanager.com",
+    "scorecardresearch.com",
+    "pixel.wp.com",
+    "/pixel",
+    "/beacon",
+    "/tracking/",
+];
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Text(&'a str),
+    Start {
+        name: String,
+        attrs: Vec<(String, String)>,
+        self_closing: bool,
+    },
+    End {
+        name: String,
+    },
+}
+
+/// Split an HTML fragment into text and tags; comments, doctypes and
+/// processing instructions are dropped, stray `<` is kept as text
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    tokenize_spans(html)
+        .into_iter()
+        .map(|(token, _)| token)
+        .collect()
+}
+
+/// `tokenize`, with the byte range each token was read from
+fn tokenize_spans(html: &str) -> Vec<(Token<'_>, Range<usize>)> {
+    let bytes = html.as_bytes();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    let mut text_start = 0;
+
+    while let Some(offset) = html[pos..].find('<') {
+        let lt = pos + offset;
+        let rest = &html[lt..];
+        let next = bytes.get(lt + 1).copied().unwrap_or(b' ');
+
+        let (token, end) = if let Some(body) = rest.strip_prefix("<!--") {
+            (
+                None,
+                lt + 4 + body.find("-->").map_or(body.len(), |i| i + 3),
+            )
+        } else if let Some(body) = rest.strip_prefix("<![CDATA[") {
+            let len = body.find("]]>").unwrap_or(body.len());
+            let end = lt + 9 + len + if len < body.len() { 3 } else { 0 };
+            (Some(Token::Text(&body[..len])), end)
+        } else if next == b'!' || next == b'?' {
+            (None, lt + rest.find('>').map_or(rest.len(), |i| i + 1))
+        } else if next == b'/' && bytes.get(lt + 2).is_some_and(u8::is_ascii_alphabetic) {
+            let name = tag_name(&rest[2..]);
+            let end = lt + rest.find('>').map_or(rest.len(), |i| i + 1);
+            (Some(Token::End { name }), end)
+        } else if next.is_ascii_alphabetic() {
+            let (token, len) = start_tag(rest);
+            (Some(token), lt + len)
+        } else {
+            pos = lt + 1;
+            continue;
+        };
+
+        if text_start < lt {
+            tokens.push((Token::Text(&html[text_start..lt]), text_start..lt));
+        }
+        pos = end;
+        text_start = end;
+
+        // Script and style bodies are not markup: jump to the closing tag
+        if let Some(Token::Start {
+            name,
+            self_closing: false,
+            ..
+        }) = &token
+            && RAW_TEXT_TAGS.contains(&name.as_str())
+        {
+            let close = format!("</{name}");
+            let body_end = find_ignore_case(&html[pos..], &close).map_or(html.len(), |i| pos + i);
+            let name = name.clone();
+            tokens.extend(token.map(|token| (token, lt..pos)));
+            if pos < body_end {
+                tokens.push((Token::Text(&html[pos..body_end]), pos..body_end));
+            }
+            pos = html[body_end..]
+                .find('>')
+                .map_or(html.len(), |i| body_end + i + 1);
+            tokens.push((Token::End { name }, body_end..pos));
+            text_start = pos;
+            continue;
+        }
+        tokens.extend(token.map(|token| (token, lt..end)));
+    }
+
+    if text_start < html.len() {
+        tokens.push((Token::Text(&html[text_start..]), text_start..html.len()));
+    }
+    tokens
+}
+
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|w| w.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+fn tag_name(rest: &str) -> String {
+    rest.chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | ':'))
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// Parse `<name attr=value …>` at the start of `rest`; returns the token and
+/// the number of bytes consumed
+fn start_tag(rest: &str) -> (Token<'static>, usize) {
+    let name = tag_name(&rest[1..]);
+    let bytes = rest.as_bytes();
+    let mut pos = 1 + name.len();
+    let mut attrs = Vec::new();
+
+    loop {
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        match bytes.get(pos) {
+            None => {
+                return (
+                    Token::Start {
+                        name,
+                        attrs,
+                        self_closing: false,
+                    },
+                    pos,
+                );
+            }
+            Some(b'>') => {
+                return (
+                    Token::Start {
+                        name,
+                        attrs,
+                        self_closing: false,
+                    },
+                    pos + 1,
+                );
+            }
+            Some(b'/') if bytes.get(pos + 1) == Some(&b'>') => {
+                return (
+                    Token::Start {
+                        name,
+                        attrs,
+                        self_closing: true,
+                    },
+                    pos + 2,
+                );
+            }
+            Some(b'/') => {
+                pos += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        let key_start = pos;
+        while pos < bytes.len()
+            && !bytes[pos].is_ascii_whitespace()
+            && !matches!(bytes[pos], b'=' | b'>' | b'/')
+        {
+            pos += 1;
+        }
+        let key = rest[key_start..pos].to_ascii_lowercase();
+
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        let mut value = String::new();
+        if bytes.get(pos) == Some(&b'=') {
+            pos += 1;
+            while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+                pos += 1;
+            }
+            let raw = match bytes.get(pos) {
+                Some(&quote @ (b'"' | b'\'')) => {
+                    let len = rest[pos + 1..]
+                        .find(quote as char)
+                        .unwrap_or(rest.len() - pos - 1);
+                    let raw = &rest[pos + 1..pos + 1 + len];
+                    pos = (pos + len + 2).min(rest.len());
+                    raw
+                }
+                _ => {
+                    let start = pos;
+                    while pos < bytes.len()
+                        && !bytes[pos].is_ascii_whitespace()
+                        && bytes[pos] != b'>'
+                    {
+                        pos += 1;
+                    }
+                    &rest[start..pos]
+                }
+            };
+            value = decode_entities(raw).into_owned();
+        }
+        if !key.is_empty() {
+            attrs.push((key, value));
+        }
+    }
+}
+
+/// Resolve character references and the common named entities; unknown
+/// names are kept verbatim
+pub fn decode_entities(text: &str) -> Cow<'_, str> {
+    if !text.contains('&') {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+
+        let resolved = rest[1..]
+            .find(';')
+            .filter(|&len| len <= 32)
+            .and_then(|len| {
+                let name = &rest[1..1 + len];
+                let ch = match name.strip_prefix('#') {
+                    Some(num) => match num.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => num.parse().ok(),
+                    }
+                    .and_then(char::from_u32)
+                    .map(|c| c.to_string()),
+                    None => named_entity(name).map(str::to_string),
+                };
+                ch.map(|ch| (ch, len + 2))
+            });
+
+        match resolved {
+            Some((ch, consumed)) => {
+                out.push_str(&ch);
+                rest = &rest[consumed..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+fn named_entity(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "amp" => "&",
+        "lt" => "<",
+        "gt" => ">",
+        "quot" => "\"",
+        "apos" => "'",
+        "nbsp" => "\u{a0}",
+        "ensp" => "\u{2002}",
+        "emsp" => "\u{2003}",
+        "thinsp" => "\u{2009}",
+        "shy" => "\u{ad}",
+        "ndash" => "–",
+        "mdash" => "—",
+        "hellip" => "…",
+        "lsquo" => "‘",
+        "rsquo" => "’",
+        "sbquo" => "‚",
+        "ldquo" => "“",
+        "rdquo" => "”",
+        "bdquo" => "„",
+        "laquo" => "«",
+        "raquo" => "»",
+        "bull" => "•",
+        "middot" => "·",
+        "copy" => "©",
+        "reg" => "®",
+        "trade" => "™",
+        "deg" => "°",
+        "plusmn" => "±",
+        "times" => "×",
+        "divide" => "÷",
+        "frac12" => "½",
+        "frac14" => "¼",
+        "frac34" => "¾",
+        "permil" => "‰",
+        "euro" => "€",
+        "pound" => "£",
+        "yen" => "¥",
+        "cent" => "¢",
+        "sect" => "§",
+        "para" => "¶",
+        "dagger" => "†",
+        "Dagger" => "‡",
+        "prime" => "′",
+        "Prime" => "″",
+        "larr" => "←",
+        "rarr" => "→",
+        "uarr" => "↑",
+        "darr" => "↓",
+        "iexcl" => "¡",
+        "iquest" => "¿",
+        "szlig" => "ß",
+        "agrave" => "à",
+        "aacute" => "á",
+        "acirc" => "â",
+        "atilde" => "ã",
+        "auml" => "ä",
+        "aring" => "å",
+        "aelig" => "æ",
+        "ccedil" => "ç",
+        "egrave" => "è",
+        "eacute" => "é",
+        "ecirc" => "ê",
+        "euml" => "ë",
+        "igrave" => "ì",
+        "iacute" => "í",
+        "icirc" => "î",
+        "iuml" => "ï",
+        "ntilde" => "ñ",
+        "ograve" => "ò",
+        "oacute" => "ó",
+        "ocirc" => "ô",
+        "otilde" => "õ",
+        "ouml" => "ö",
+        "oslash" => "ø",
+        "ugrave" => "ù",
+        "uacute" => "ú",
+        "ucirc" => "û",
+        "uuml" => "ü",
+        "yacute" => "ý",
+        "yuml" => "ÿ",
+        "Agrave" => "À",
+        "Aacute" => "Á",
+        "Acirc" => "Â",
+        "Auml" => "Ä",
+        "Aring" => "Å",
+        "Ccedil" => "Ç",
+        "Egrave" => "È",
+        "Eacute" => "É",
+        "Ecirc" => "Ê",
+        "Ntilde" => "Ñ",
+        "Oacute" => "Ó",
+        "Ouml" => "Ö",
+        "Oslash" => "Ø",
+        "Uacute" => "Ú",
+        "Uuml" => "Ü",
+        _ => return None,
+    })
+}
+
+/// Plain text of an HTML fragment: entities decoded, tags stripped (along
+/// with scripts, styles and embeds), whitespace collapsed and the result cut
+/// at a word boundary to at most `max_chars` characters
+pub fn to_plain_text(html: &str, max_chars: usize) -> String {
+    let mut text = String::new();
+    let mut dropped = 0usize;
+
+    for token in tokenize(html) {
+        match token {
+            Token::Text(t) if dropped == 0 => text.push_str(&decode_entities(t)),
+            Token::Text(_) => {}
+            Token::Start {
+                name, self_closing, ..
+            } => {
+                if DROPPED_TAGS.contains(&name.as_str()) {
+                    if !self_closing && !VOID_TAGS.contains(&name.as_str()) {
+                        dropped += 1;
+                    }
+                } else if BLOCK_TAGS.contains(&name.as_str()) {
+                    text.push(' ');
+                }
+            }
+            Token::End { name } => {
+                if DROPPED_TAGS.contains(&name.as_str()) {
+                    dropped = dropped.saturating_sub(1);
+                } else if BLOCK_TAGS.contains(&name.as_str()) {
+                    text.push(' ');
+                }
+            }
+        }
+    }
+
+    truncate_words(&collapse_whitespace(&text), max_chars)
+}
+
+/// Runs of whitespace (including no-break spaces) become one space
+fn collapse_whitespace(text: &str) -> String {
+    text.split(|c: char| c.is_whitespace() || c == '\u{ad}')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn truncate_words(text: &str, max_chars: usize) -> String {
+    if max_chars == 0 || text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+
+    // Leave room for the ellipsis
+    let cut = text
+        .char_indices()
+        .nth(max_chars - 1)
+        .map_or(text.len(), |(i, _)| i);
+    let head = &text[..cut];
+    let head = match head.rfind(' ') {
+        Some(space) if space >= cut / 2 => &head[..space],
+        _ => head,
+    };
+    format!(
+        "{}…",
+        head.trim_end_matches(|c: char| c.is_whitespace() || c == ',')
+    )
+}
+
+/// Allow-list applied by `sanitize_html`
+static SANITIZER: LazyLock<ammonia::Builder<'static>> = LazyLock::new(|| {
+    let mut builder = ammonia::Builder::empty();
+    builder
+        .tags(ALLOWED_TAGS.iter().copied().collect())
+        .clean_content_tags(DROPPED_TAGS.iter().copied().collect())
+        .generic_attributes(HashSet::new())
+        .tag_attributes(HashMap::from([
+            ("a", HashSet::from(["href", "title"])),
+            (
+                "img",
+                HashSet::from(["src", "alt", "title", "width", "height"]),
+            ),
+            ("td", HashSet::from(["colspan", "rowspan"])),
+            ("th", HashSet::from(["colspan", "rowspan"])),
+        ]))
+        .url_schemes(HashSet::from(["http", "https", "mailto"]))
+        .url_relative(ammonia::UrlRelative::PassThrough)
+        .link_rel(Some("noopener noreferrer"))
+        .strip_comments(true);
+    builder
+});
+
+/// The fragment with only harmless markup left: allow-listed tags and
+/// attributes, `http(s)` URLs, no scripts, frames, embeds or tracking
+/// pixels. Unknown tags are unwrapped and every open tag is closed.
+pub fn sanitize_html(html: &str) -> String {
+    SANITIZER
+        .clean(&strip_trackers(html))
+        .to_string()
+        .trim()
+        .to_string()
+}
+
+/// The fragment without tracking pixels, images lacking an absolute
+/// `http(s)` source, and the start tags of links to trackers (their text is
+/// kept). Only a filter: `sanitize_html` cleans whatever it leaves.
+fn strip_trackers(html: &str) -> Cow<'_, str> {
+    let mut out = String::new();
+    let mut copied = 0;
+    for (token, span) in tokenize_spans(html) {
+        let Token::Start { name, attrs, .. } = token else {
+            continue;
+        };
+        let get = |key: &str| attrs.iter().find(|(k, _)| k == key).map(|(_, v)| v.trim());
+        let strip = match name.as_str() {
+            "a" => get("href").is_some_and(is_tracker),
+            "img" => {
+                let tiny = |key| {
+                    get(key)
+                        .and_then(|v| v.parse::<u32>().ok())
+                        .is_some_and(|v| v <= 1)
+                };
+                get("src").is_none_or(|src| !is_absolute_http(src) || is_tracker(src))
+                    || tiny("width")
+                    || tiny("height")
+            }
+            _ => false,
+        };
+        if strip {
+            out.push_str(&html[copied..span.start]);
+            copied = span.end;
+        }
+    }
+    if copied == 0 {
+        return Cow::Borrowed(html);
+    }
+    out.push_str(&html[copied..]);
+    Cow::Owned(out)
+}
+
+fn is_absolute_http(url: &str) -> bool {
+    let lower = url.to_ascii_lowercase();
+    lower.starts_with("http://") || lower.starts_with("https://")
+}
+
+fn is_tracker(url: &str) -> bool {
+    let lower = url.to_ascii_lowercase();
+    TRACKER_PATTERNS.iter().any(|p| lower.contains(p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLOOMBERG: &str = r#"<p>Investors&rsquo; daily waves of option sales are poised to <b>slow</b> a rally.</p>
+<script>track("open")</script><style>p { color: red }</style>
+<img src="https://pixel.wp.com/g.gif?blog=1" width="1" height="1" />
+<img src="https://assets.bwbx.io/images/chart.png" alt="Chart" onerror="alert(1)" style="x">
+<iframe src="https://www.youtube.com/embed/x"><p>fallback</p></iframe>
+<a href="javascript:alert(1)">click</a> <a href="https://www.bloomberg.com/news" class="x">More</a>
+<a href="http://feeds.feedburner.com/~ff/bloomberg?a=1"><img src="http://feeds.feedburner.com/~ff/bloomberg?i=1"></a>"#;
+
+    #[test]
+    fn test_to_plain_text() {
+        assert_eq!(
+            to_plain_text(BLOOMBERG, SUMMARY_TEXT_MAX_CHARS),
+            "Investors’ daily waves of option sales are poised to slow a rally. click More"
+        );
+        assert_eq!(
+            to_plain_text(
+                "<p>One</p><p>Two&nbsp;&amp;&#160;three</p>line<br/>break",
+                100
+            ),
+            "One Two & three line break"
+        );
+        assert_eq!(
+            to_plain_text("Caf&eacute; &unknown; 5 < 6", 100),
+            "Café &unknown; 5 < 6"
+        );
+        assert_eq!(to_plain_text("<!-- comment -->  ", 100), "");
+    }
+
+    #[test]
+    fn test_to_plain_text_truncates_at_word_boundary() {
+        let text = to_plain_text("<p>The central bank held rates steady again</p>", 20);
+        assert_eq!(text, "The central bank…");
+        assert!(text.chars().count() <= 20);
+
+        // A single long word is cut mid-word
+        assert_eq!(to_plain_text("Supercalifragilistic", 6), "Super…");
+    }
+
+    #[test]
+    fn test_sanitize_html() {
+        assert_eq!(
+            sanitize_html(BLOOMBERG),
+            "<p>Investors’ daily waves of option sales are poised to <b>slow</b> a rally.</p>\n\n\n\
+             <img src=\"https://assets.bwbx.io/images/chart.png\" alt=\"Chart\">\n\n\
+             <a rel=\"noopener noreferrer\">click</a> <a href=\"https://www.bloomberg.com/news\" rel=\"noopener noreferrer\">More</a>"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_html_balances_tags() {
+        assert_eq!(
+            sanitize_html("<div><p>open <em>never closed</div> tail</span>"),
+            // Like a browser, the unclosed <em> is reopened after the <div>
+            "<div><p>open <em>never closed</em></p></div><em> tail</em>"
+        );
+        assert_eq!(
+            sanitize_html("<unknown>kept</unknown> 1 &lt; 2 <b title=x>b</b>"),
+            "kept 1 &lt; 2 <b>b</b>"
+        );
+        assert_eq!(
+            sanitize_html(r#"<a href="/relative?a=1&amp;b=2" title='say "hi"'>x</a>"#),
+            r#"<a href="/relative?a=1&amp;b=2" title="say &quot;hi&quot;" rel="noopener noreferrer">x</a>"#
+        );
+        assert_eq!(sanitize_html("<SCRIPT>var a = '</p>';</SCRIPT>ok"), "ok");
+    }
+
+    #[test]
+    fn test_sanitize_html_adversarial() {
+        let safe = |html: &str| {
+            let clean = sanitize_html(html);
+            // Escaped text such as "&lt;script" is harmless; live markup is not
+            let lower = clean.to_ascii_lowercase();
+            assert!(
+                ["<script", "<iframe", "javascript:", "onerror="]
+                    .iter()
+                    .all(|needle| !lower.contains(needle)),
+                "{html:?} sanitized to {clean:?}"
+            );
+            clean
+        };
+
+        assert_eq!(
+            safe(r#"<a href="java&#x09;script:alert(1)">x</a>"#),
+            r#"<a rel="noopener noreferrer">x</a>"#
+        );
+        assert_eq!(
+            safe(r#"<a href=" javascript:alert(1)">x</a>"#),
+            r#"<a rel="noopener noreferrer">x</a>"#
+        );
+        assert_eq!(
+            safe(r#"<a href="JaVaScRiPt:alert(1)" title="t">x</a>"#),
+            r#"<a title="t" rel="noopener noreferrer">x</a>"#
+        );
+        assert_eq!(safe(r#"before <iframe src="https://e.com/x""#), "before");
+        assert_eq!(safe("<p>a</p><iframe><p>b</p>"), "<p>a</p>");
+        assert_eq!(
+            safe(
+                r#"<img src="x" onerror="alert(1)"><img/src="https://e.com/a.png"/onerror=alert(1)>"#
+            ),
+            r#"<img src="https://e.com/a.png">"#
+        );
+        assert_eq!(
+            safe(r#"<svg><a xlink:href="javascript:alert(1)">x</a></svg><math>y</math>ok"#),
+            "ok"
+        );
+        assert_eq!(
+            safe(r#"<a href="data:text/html;base64,PHNjcmlwdD4=">x</a>"#),
+            r#"<a rel="noopener noreferrer">x</a>"#
+        );
+        safe(r#"<scr<script>ipt>alert(1)</script>"#);
+        safe(r#"<<script>script>alert(1)<</script>/script>"#);
+        safe(r#"<!--<script>--><script>alert(1)</script>-->"#);
+    }
+}
@@ -0,0 +1,154 @@
+use crate::rss::link::LinkCanonicalizer;
+use crate::rss::{RssItem, generate_rss_item_id, generate_undated_rss_item_id, hash_to_uuid};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// How an item's deduplication ID is derived
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum IdentityStrategy {
+    /// Publisher GUID (RSS `<guid>`, Atom `<id>`, JSON Feed `id`), falling
+    /// back to the link for items without one
+    Guid,
+    /// Canonical item link (`RssItem::canonical_link`)
+    Link,
+    /// Source + title + publish date; the original scheme, so a corrected
+    /// headline or bumped date creates a new row
+    #[default]
+    Legacy,
+}
+
+impl IdentityStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Guid => "guid",
+            Self::Link => "link",
+            Self::Legacy => "legacy",
+        }
+    }
+
+    pub fn item_id(&self, item: &RssItem) -> Uuid {
+        match self {
+            Self::Guid => match item.guid.as_deref().map(str::trim) {
+                Some(guid) if !guid.is_empty() => hash_to_uuid(&[&item.source, "guid:", guid]),
+                _ => Self::Link.item_id(item),
+            },
+            Self::Link => {
+                // Items that never went through ingestion get the default rules
+                let link = match &item.canonical_link {
+                    Some(link) => link.clone(),
+                    None => LinkCanonicalizer::default().canonicalize(&item.link, None),
+                };
+                hash_to_uuid(&[&item.source, "link:", &link])
+            }
+            Self::Legacy if item.published_at_fallback => {
+                generate_undated_rss_item_id(&item.source, &item.title, &item.link)
+            }
+            Self::Legacy => generate_rss_item_id(&item.source, &item.title, &item.published_at),
+        }
+    }
+
+    /// Re-key parsed items; `RssItem::new` always produces legacy IDs, so
+    /// run it after `LinkCanonicalizer::apply`
+    pub fn assign(&self, items: &mut [RssItem]) {
+        if *self == Self::Legacy {
+            return;
+        }
+        for item in items {
+            item.id = self.item_id(item);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+
+    fn item(title: &str, link: &str, guid: Option<&str>, published_at: &str) -> RssItem {
+        let published_at = DateTime::parse_from_rfc3339(published_at)
+            .unwrap()
+            .with_timezone(&Utc);
+        RssItem::new("wire", title, link, None, Some(published_at))
+            .with_guid(guid.map(str::to_string))
+    }
+
+    #[test]
+    fn test_identity_survives_headline_and_date_edits() {
+        let original = item(
+            "Fed hlods rates",
+            "https://example.com/fed",
+            Some("fed-2025-11-16"),
+            "2025-11-16T09:00:00Z",
+        );
+        let corrected = item(
+            "Fed holds rates",
+            "https://EXAMPLE.com/fed#top",
+            Some("fed-2025-11-16"),
+            "2025-11-16T09:30:00Z",
+        );
+
+        for strategy in [IdentityStrategy::Guid, IdentityStrategy::Link] {
+            assert_eq!(
+                strategy.item_id(&original),
+                strategy.item_id(&corrected),
+                "{strategy:?}"
+            );
+        }
+        assert_ne!(
+            IdentityStrategy::Legacy.item_id(&original),
+            IdentityStrategy::Legacy.item_id(&corrected)
+        );
+    }
+
+    #[test]
+    fn test_identity_strategies() {
+        let with_guid = item("A", "https://e.com/a", Some("g-1"), "2025-11-16T09:00:00Z");
+        let without_guid = item("A", "https://e.com/a", None, "2025-11-16T09:00:00Z");
+
+        // Legacy matches the IDs already stored by `RssItem::new`
+        assert_eq!(IdentityStrategy::Legacy.item_id(&with_guid), with_guid.id);
+
+        // GUID-less items fall back to the link
+        assert_eq!(
+            IdentityStrategy::Guid.item_id(&without_guid),
+            IdentityStrategy::Link.item_id(&without_guid)
+        );
+        assert_ne!(
+            IdentityStrategy::Guid.item_id(&with_guid),
+            IdentityStrategy::Link.item_id(&with_guid)
+        );
+
+        let mut items = vec![with_guid];
+        IdentityStrategy::Guid.assign(&mut items);
+        assert_eq!(items[0].id, IdentityStrategy::Guid.item_id(&items[0]));
+    }
+
+    #[test]
+    fn test_link_identity_uses_canonical_link() {
+        let links = LinkCanonicalizer::default();
+        let mut items = vec![
+            item("A", "/a?utm_source=rss", None, "2025-11-16T09:00:00Z"),
+            item(
+                "A",
+                "https://e.com/a?srnd=markets",
+                None,
+                "2025-11-16T09:00:00Z",
+            ),
+        ];
+        links.apply(&mut items, Some("https://e.com/feed"));
+        IdentityStrategy::Link.assign(&mut items);
+        assert_eq!(items[0].id, items[1].id);
+
+        // Without a canonical link the default rules apply to the raw link
+        let raw = item(
+            "A",
+            "https://E.com/a?fbclid=x#top",
+            None,
+            "2025-11-16T09:00:00Z",
+        );
+        assert_eq!(IdentityStrategy::Link.item_id(&raw), items[0].id);
+    }
+}
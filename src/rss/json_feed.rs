@@ -0,0 +1,229 @@
+use crate::error::RssParseError;
+use crate::rss::date::parse_date;
+use crate::rss::{ParseOutcome, RssCategory, RssItem, RssParser, RssResult};
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// JSON Feed 1.1 document (1.0 `author` is accepted as well)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JsonFeed {
+    pub version: String,
+    pub title: Option<String>,
+    pub home_page_url: Option<String>,
+    pub feed_url: Option<String>,
+    pub authors: Vec<JsonFeedAuthor>,
+    pub items: Vec<JsonFeedItem>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JsonFeedItem {
+    /// Spec says string, but numbers are common in the wild
+    #[serde(deserialize_with = "string_or_number")]
+    pub id: Option<String>,
+    pub url: Option<String>,
+    pub external_url: Option<String>,
+    pub title: Option<String>,
+    pub summary: Option<String>,
+    pub content_html: Option<String>,
+    pub content_text: Option<String>,
+    pub date_published: Option<String>,
+    pub date_modified: Option<String>,
+    pub authors: Vec<JsonFeedAuthor>,
+    /// JSON Feed 1.0 single author
+    pub author: Option<JsonFeedAuthor>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JsonFeedAuthor {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub avatar: Option<String>,
+}
+
+fn string_or_number<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(s) => Some(s),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    })
+}
+
+impl JsonFeedItem {
+    /// Author names, falling back to the 1.0 `author` field
+    pub fn author_names(&self) -> Vec<String> {
+        self.authors
+            .iter()
+            .chain(self.author.iter())
+            .filter_map(|a| a.name.clone())
+            .collect()
+    }
+
+    /// `date_published`, falling back to `date_modified`
+    pub fn date(&self) -> Option<&str> {
+        self.date_published
+            .as_deref()
+            .or(self.date_modified.as_deref())
+    }
+
+    /// First field an item needs that this one lacks
+    pub fn missing_field(&self) -> Option<&'static str> {
+        if self.url.is_none() && self.external_url.is_none() {
+            Some("url")
+        } else if self.title.is_none() && self.summary.is_none() {
+            Some("title")
+        } else if self.date().is_none() {
+            Some("date_published")
+        } else {
+            None
+        }
+    }
+
+    pub fn into_rss_item(self, source: &str) -> Option<RssItem> {
+        let authors = self.author_names();
+        let link = self.url.or(self.external_url)?;
+        // Title-less microblog posts fall back to their summary
+        let title = self.title.or_else(|| self.summary.clone())?;
+        let date = self.date_published.or(self.date_modified)?;
+        let summary = self.summary.or(self.content_html).or(self.content_text);
+
+        Some(
+            RssItem::new(source, title, link, summary, parse_date(&date))
+                .with_guid(self.id)
+                .with_authors(authors)
+                .with_categories(
+                    self.tags
+                        .into_iter()
+                        .map(|tag| RssCategory::new(tag, None))
+                        .collect(),
+                ),
+        )
+    }
+}
+
+pub fn parse_json_feed(json: &str) -> RssResult<JsonFeed> {
+    let feed: JsonFeed =
+        serde_json::from_str(json).map_err(|e| RssParseError::Json(e.to_string()))?;
+
+    if !feed.version.starts_with("https://jsonfeed.org/version/") {
+        return Err(RssParseError::Json(format!(
+            "unsupported JSON Feed version '{}'",
+            feed.version
+        )));
+    }
+
+    Ok(feed)
+}
+
+/// JSON Feed parser; `source` is stored on every produced item
+#[derive(Debug, Clone)]
+pub struct JsonFeedParser {
+    source: String,
+}
+
+impl JsonFeedParser {
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+        }
+    }
+}
+
+impl RssParser for JsonFeedParser {
+    fn parse_outcome(&self, json: &str) -> RssResult<ParseOutcome> {
+        let feed = parse_json_feed(json)?;
+        let mut outcome = ParseOutcome::new(Vec::new(), feed.home_page_url);
+        for (index, item) in feed.items.into_iter().enumerate() {
+            if let Some(field) = item.missing_field() {
+                outcome.skip(index, None, field);
+                continue;
+            }
+            let date = item.date().unwrap_or_default().to_string();
+            if let Some(item) = item.into_rss_item(&self.source) {
+                outcome.push(index, None, item, &date);
+            }
+        }
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"{
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": "Internal Alerts",
+        "home_page_url": "https://alerts.example.com/",
+        "items": [
+            {
+                "id": "2",
+                "url": "https://alerts.example.com/2",
+                "title": "Circuit breaker triggered",
+                "content_html": "<p>Trading halted</p>",
+                "date_published": "2025-11-16T09:30:00-05:00",
+                "authors": [{ "name": "Risk Desk" }],
+                "tags": ["halt", "equities"]
+            },
+            {
+                "id": 1,
+                "url": "https://alerts.example.com/1",
+                "summary": "Title-less post",
+                "date_modified": "2025-11-15T00:00:00Z",
+                "author": { "name": "Ops" }
+            },
+            {
+                "id": "0",
+                "title": "No link"
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_parse_json_feed() {
+        let feed = parse_json_feed(SAMPLE).expect("Failed to parse JSON Feed");
+        assert_eq!(feed.title.as_deref(), Some("Internal Alerts"));
+        assert_eq!(feed.items.len(), 3);
+        assert_eq!(feed.items[0].tags, vec!["halt", "equities"]);
+        assert_eq!(feed.items[1].id.as_deref(), Some("1"));
+        assert_eq!(feed.items[1].author_names(), vec!["Ops"]);
+    }
+
+    #[test]
+    fn test_json_feed_parser_maps_items() {
+        let items = JsonFeedParser::new("alerts")
+            .parse(SAMPLE)
+            .expect("Failed to parse JSON Feed");
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].title, "Circuit breaker triggered");
+        assert_eq!(items[0].summary.as_deref(), Some("<p>Trading halted</p>"));
+        assert_eq!(
+            items[0].published_at.to_rfc3339(),
+            "2025-11-16T14:30:00+00:00"
+        );
+        assert_eq!(items[1].title, "Title-less post");
+
+        let outcome = JsonFeedParser::new("alerts").parse_outcome(SAMPLE).unwrap();
+        assert_eq!(outcome.skipped(), 1);
+        assert_eq!(outcome.diagnostics[0].item_index, Some(2));
+        assert_eq!(
+            outcome.diagnostics[0].to_string(),
+            "item 2: skipped, missing url"
+        );
+    }
+
+    #[test]
+    fn test_rejects_non_json_feed() {
+        assert!(matches!(
+            JsonFeedParser::new("x").parse(r#"{"items": []}"#),
+            Err(RssParseError::Json(_))
+        ));
+        assert!(JsonFeedParser::new("x").parse("<rss/>").is_err());
+    }
+}
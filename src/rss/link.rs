@@ -0,0 +1,209 @@
+use crate::rss::RssItem;
+use reqwest::Url;
+
+/// Query parameters that only record where a click came from. A trailing
+/// `*` matches any parameter with that prefix.
+pub const TRACKING_PARAMS: &[&str] = &[
+    "utm_*",
+    "srnd",
+    "fbclid",
+    "gclid",
+    "dclid",
+    "gbraid",
+    "wbraid",
+    "msclkid",
+    "yclid",
+    "mc_cid",
+    "mc_eid",
+    "_hsenc",
+    "_hsmi",
+    "mkt_tok",
+    "igshid",
+    "cmpid",
+    "ncid",
+    "ocid",
+    "smid",
+    "sr_share",
+    "ref_src",
+    "ref_url",
+    "at_medium",
+    "at_campaign",
+];
+
+/// Redirect endpoints (`google.com/url?q=…`, `l.facebook.com/l.php?u=…`)
+/// whose target is unwrapped when it is an absolute http(s) URL
+const REDIRECT_PATHS: &[&str] = &["/url", "/l.php", "/redirect", "/redir", "/out", "/link"];
+const REDIRECT_PARAMS: &[&str] = &["url", "q", "u", "target", "dest", "destination", "to"];
+
+/// Nested redirects followed at most
+const MAX_REDIRECTS: usize = 3;
+
+/// Rewrites item links into the form used to recognize the same article
+/// under different URLs
+#[derive(Debug, Clone)]
+pub struct LinkCanonicalizer {
+    /// Lowercase parameter names; entries ending in `*` are prefixes
+    strip_params: Vec<String>,
+}
+
+impl Default for LinkCanonicalizer {
+    fn default() -> Self {
+        Self::new(TRACKING_PARAMS)
+    }
+}
+
+impl LinkCanonicalizer {
+    pub fn new(strip_params: &[impl AsRef<str>]) -> Self {
+        Self {
+            strip_params: strip_params
+                .iter()
+                .map(|p| p.as_ref().trim().to_ascii_lowercase())
+                .filter(|p| !p.is_empty())
+                .collect(),
+        }
+    }
+
+    fn strips(&self, param: &str) -> bool {
+        let param = param.to_ascii_lowercase();
+        self.strip_params
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => param.starts_with(prefix),
+                None => param == *pattern,
+            })
+    }
+
+    /// Absolute form of `link` (relative links are resolved against `base`)
+    /// with the scheme and host lowercased, redirect wrappers unwrapped, and
+    /// the fragment and tracking parameters removed. Links that aren't URLs
+    /// are only trimmed.
+    pub fn canonicalize(&self, link: &str, base: Option<&Url>) -> String {
+        let link = link.trim();
+        let parsed = match base {
+            Some(base) => base.join(link),
+            None => Url::parse(link),
+        };
+        let Ok(mut url) = parsed else {
+            return link.to_string();
+        };
+        if !matches!(url.scheme(), "http" | "https") {
+            return url.to_string();
+        }
+
+        for _ in 0..MAX_REDIRECTS {
+            match redirect_target(&url) {
+                Some(target) => url = target,
+                None => break,
+            }
+        }
+
+        url.set_fragment(None);
+        // Kept parameters stay exactly as written, encoding and order included
+        if let Some(query) = url.query() {
+            let kept: Vec<&str> = query
+                .split('&')
+                .filter(|pair| {
+                    let name = pair.split('=').next().unwrap_or_default();
+                    !name.is_empty() && !self.strips(name)
+                })
+                .collect();
+            let query = kept.join("&");
+            url.set_query(Some(query.as_str()).filter(|q| !q.is_empty()));
+        }
+        url.to_string()
+    }
+
+    /// Set `canonical_link` on every item; `base` is the channel's own link
+    pub fn apply(&self, items: &mut [RssItem], base: Option<&str>) {
+        let base = base.and_then(|b| Url::parse(b.trim()).ok());
+        for item in items {
+            item.canonical_link = Some(self.canonicalize(&item.link, base.as_ref()));
+        }
+    }
+}
+
+/// The URL a known redirect endpoint points to
+fn redirect_target(url: &Url) -> Option<Url> {
+    if !REDIRECT_PATHS.contains(&url.path()) {
+        return None;
+    }
+    url.query_pairs()
+        .filter(|(name, _)| REDIRECT_PARAMS.contains(&name.as_ref()))
+        .find_map(|(_, value)| {
+            Url::parse(&value)
+                .ok()
+                .filter(|target| matches!(target.scheme(), "http" | "https"))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonicalize_strips_tracking() {
+        let links = LinkCanonicalizer::default();
+        assert_eq!(
+            links.canonicalize(
+                "HTTPS://WWW.Bloomberg.com/news/articles/2025-11-16/fed?srnd=homepage&utm_source=rss#top",
+                None
+            ),
+            "https://www.bloomberg.com/news/articles/2025-11-16/fed"
+        );
+        // Other parameters keep their order; the path keeps its case
+        assert_eq!(
+            links.canonicalize(
+                "https://example.com:443/Story?id=7&UTM_Medium=x&page=2&fbclid=abc",
+                None
+            ),
+            "https://example.com/Story?id=7&page=2"
+        );
+        assert_eq!(links.canonicalize("  not a url ", None), "not a url");
+
+        let custom = LinkCanonicalizer::new(&["ref", "cmp*"]);
+        assert_eq!(
+            custom.canonicalize("https://e.com/a?ref=rss&cmpid=1&utm_source=x", None),
+            "https://e.com/a?utm_source=x"
+        );
+    }
+
+    #[test]
+    fn test_canonicalize_resolves_and_unwraps() {
+        let links = LinkCanonicalizer::default();
+        let base = Url::parse("https://Example.com/markets/index.html").unwrap();
+        assert_eq!(
+            links.canonicalize("/news/1?utm_campaign=feed", Some(&base)),
+            "https://example.com/news/1"
+        );
+        assert_eq!(
+            links.canonicalize("2.html", Some(&base)),
+            "https://example.com/markets/2.html"
+        );
+        // An absolute link ignores the base
+        assert_eq!(
+            links.canonicalize("https://other.org/x", Some(&base)),
+            "https://other.org/x"
+        );
+
+        assert_eq!(
+            links.canonicalize(
+                "https://www.google.com/url?rct=j&url=https%3A%2F%2Fnews.example.com%2Fa%3Futm_source%3Dalerts&ct=ga",
+                None
+            ),
+            "https://news.example.com/a"
+        );
+        // A search for a URL is not a redirect
+        assert_eq!(
+            links.canonicalize("https://e.com/search?q=https://x.org", None),
+            "https://e.com/search?q=https://x.org"
+        );
+    }
+
+    #[test]
+    fn test_apply_sets_canonical_link() {
+        let mut items = vec![RssItem::new("src", "t", "/a?gclid=1", None, None)];
+        LinkCanonicalizer::default().apply(&mut items, Some("https://e.com/feed"));
+        assert_eq!(items[0].link, "/a?gclid=1");
+        assert_eq!(items[0].canonical_link.as_deref(), Some("https://e.com/a"));
+    }
+}
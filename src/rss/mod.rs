@@ -1,11 +1,27 @@
+pub mod atom;
 pub mod bloomberg;
 pub mod coindesk;
+pub mod date;
+pub mod encoding;
+pub mod generic;
+pub mod html;
+pub mod identity;
+pub mod json_feed;
+pub mod link;
 pub mod reuters;
+pub mod signature;
+mod xml;
 
-use chrono::{DateTime, Utc};
 use crate::error::RssParseError;
+use atom::AtomParser;
+use chrono::{DateTime, Utc};
+use generic::GenericRssParser;
+use json_feed::JsonFeedParser;
+use quick_xml::Reader;
+use quick_xml::events::Event;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::fmt;
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -13,16 +29,66 @@ pub struct RssItem {
     pub id: Uuid,
     pub source: String,
     pub title: String,
+    /// Link as published
     pub link: String,
+    /// `link` made absolute and stripped of tracking (see `link::LinkCanonicalizer`)
+    pub canonical_link: Option<String>,
+    /// Summary as published, usually an HTML fragment
     pub summary: Option<String>,
+    /// Plain text of `summary` (see `normalize_summary`)
+    pub summary_text: Option<String>,
+    /// `summary` with scripts, embeds and trackers removed
+    pub summary_html: Option<String>,
     pub published_at: DateTime<Utc>,
+    /// The feed had no usable date, so `published_at` is the first-seen time
+    pub published_at_fallback: bool,
+    /// Publisher identifier (`<guid>`, Atom `<id>`, JSON Feed `id`)
+    pub guid: Option<String>,
+    pub authors: Vec<String>,
+    pub categories: Vec<RssCategory>,
+    /// Name of the configured feed that produced the item, set at ingestion
+    pub feed: Option<String>,
+    /// Earlier versions kept in `rss_item_revisions`; filled when read from
+    /// the database (see `db::get_rss_item`), 0 for parsed items
+    pub revision_count: i32,
+    /// When a changed version last replaced the stored one
+    pub last_updated_at: Option<DateTime<Utc>>,
+}
+
+/// Item category; `domain` holds the RSS `domain` or Atom `scheme`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RssCategory {
+    pub name: String,
+    pub domain: Option<String>,
+}
+
+impl RssCategory {
+    pub fn new(name: impl Into<String>, domain: Option<String>) -> Self {
+        Self {
+            name: name.into(),
+            domain,
+        }
+    }
+}
+
+pub(crate) fn generate_rss_item_id(
+    source: &str,
+    title: &str,
+    published_at: &DateTime<Utc>,
+) -> Uuid {
+    hash_to_uuid(&[source, title, &published_at.to_rfc3339()])
 }
 
-fn generate_rss_item_id(source: &str, title: &str, published_at: &DateTime<Utc>) -> Uuid {
+/// Undated items can't hash a timestamp; the link keeps their ID stable
+pub(crate) fn generate_undated_rss_item_id(source: &str, title: &str, link: &str) -> Uuid {
+    hash_to_uuid(&[source, title, link])
+}
+
+pub(crate) fn hash_to_uuid(parts: &[&str]) -> Uuid {
     let mut hasher = Sha256::new();
-    hasher.update(source.as_bytes());
-    hasher.update(title.as_bytes());
-    hasher.update(published_at.to_rfc3339().as_bytes());
+    for part in parts {
+        hasher.update(part.as_bytes());
+    }
 
     let hash = hasher.finalize();
     let mut bytes = [0u8; 16];
@@ -41,26 +107,312 @@ impl RssItem {
         let source_str = source_name.into();
         let title_str = title.into();
         let link_str = link.into();
-        let published_at_dt = published_at.unwrap_or_else(Utc::now);
-
-        let id = generate_rss_item_id(&source_str, &title_str, &published_at_dt);
+        let (id, published_at_dt) = match published_at {
+            Some(dt) => (generate_rss_item_id(&source_str, &title_str, &dt), dt),
+            None => (
+                generate_undated_rss_item_id(&source_str, &title_str, &link_str),
+                Utc::now(),
+            ),
+        };
 
         Self {
             id,
             source: source_str,
             title: title_str,
             link: link_str,
+            canonical_link: None,
             summary,
+            summary_text: None,
+            summary_html: None,
             published_at: published_at_dt,
+            published_at_fallback: published_at.is_none(),
+            guid: None,
+            authors: Vec::new(),
+            categories: Vec::new(),
+            feed: None,
+            revision_count: 0,
+            last_updated_at: None,
         }
     }
+
+    /// SHA-256 (hex) of the fields whose change makes a new revision. The
+    /// canonical link is hashed when known, so a changed tracking parameter
+    /// is not a revision.
+    pub fn content_hash(&self) -> String {
+        self.hash_content(self.canonical_link.as_ref().unwrap_or(&self.link))
+    }
+
+    /// `content_hash` over the link as published; the form stored before
+    /// canonical links existed, and the one migration 105 backfilled
+    pub fn raw_content_hash(&self) -> String {
+        self.hash_content(&self.link)
+    }
+
+    fn hash_content(&self, link: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.title.as_bytes());
+        hasher.update([0x1f]);
+        hasher.update(link.as_bytes());
+        hasher.update([0x1f]);
+        hasher.update(self.summary.as_deref().unwrap_or_default().as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Derive `summary_text` and `summary_html` from the raw summary; the
+    /// plain text is capped at `max_chars` characters
+    pub fn normalize_summary(&mut self, max_chars: usize) {
+        let raw = self.summary.as_deref().unwrap_or_default();
+        self.summary_text = Some(html::to_plain_text(raw, max_chars)).filter(|t| !t.is_empty());
+        self.summary_html = Some(html::sanitize_html(raw)).filter(|h| !h.is_empty());
+    }
+
+    pub fn with_guid(mut self, guid: Option<String>) -> Self {
+        self.guid = guid;
+        self
+    }
+
+    pub fn with_authors(mut self, authors: Vec<String>) -> Self {
+        self.authors = authors;
+        self
+    }
+
+    pub fn with_categories(mut self, categories: Vec<RssCategory>) -> Self {
+        self.categories = categories;
+        self
+    }
 }
 
 pub type RssResult<T> = Result<T, RssParseError>;
 
 /// Trait: every RSS feed should implement this trait
-pub trait RssParser {
-    fn parse(&self, xml: &str) -> RssResult<Vec<RssItem>>;
+pub trait RssParser: Send + Sync {
+    /// Items plus a warning for every entry that was skipped or degraded
+    fn parse_outcome(&self, xml: &str) -> RssResult<ParseOutcome>;
+
+    fn parse(&self, xml: &str) -> RssResult<Vec<RssItem>> {
+        self.parse_outcome(xml).map(|outcome| outcome.items)
+    }
+}
+
+/// What went wrong with one entry of a payload
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DiagnosticKind {
+    /// The entry lacks a field every item needs; it was skipped
+    MissingField { field: String },
+    /// The publish date could not be parsed; the item was kept undated
+    InvalidDate { value: String },
+    /// Some text could not be decoded and was left out of the item
+    Decoding { detail: String },
+    /// The item's canonical link belongs to another item with a different
+    /// GUID; it was not stored
+    LinkConflict { title: String, link: String },
+}
+
+/// A per-entry parse warning
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParseDiagnostic {
+    /// Position of the entry among the payload's entries; `None` for
+    /// problems outside any entry
+    pub item_index: Option<usize>,
+    /// Byte offset of the entry's start tag (XML payloads only)
+    pub byte_offset: Option<u64>,
+    #[serde(flatten)]
+    pub kind: DiagnosticKind,
+}
+
+impl ParseDiagnostic {
+    /// Whether the entry was dropped rather than stored with a degraded field
+    pub fn skipped(&self) -> bool {
+        matches!(self.kind, DiagnosticKind::MissingField { .. })
+    }
+}
+
+impl fmt::Display for ParseDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.item_index {
+            Some(index) => write!(f, "item {index}")?,
+            None => f.write_str("feed")?,
+        }
+        if let Some(offset) = self.byte_offset {
+            write!(f, " (byte {offset})")?;
+        }
+        match &self.kind {
+            DiagnosticKind::MissingField { field } => write!(f, ": skipped, missing {field}"),
+            DiagnosticKind::InvalidDate { value } => {
+                write!(f, ": unparseable publish date '{value}'")
+            }
+            DiagnosticKind::Decoding { detail } => write!(f, ": undecodable text: {detail}"),
+            DiagnosticKind::LinkConflict { title, link } => {
+                write!(f, ": '{title}' not stored, {link} belongs to another item")
+            }
+        }
+    }
+}
+
+/// Items produced from a payload and the warnings raised along the way
+#[derive(Debug, Default, Serialize)]
+pub struct ParseOutcome {
+    pub items: Vec<RssItem>,
+    pub diagnostics: Vec<ParseDiagnostic>,
+    /// The channel's own link, which relative item links are resolved against
+    pub base_url: Option<String>,
+}
+
+impl ParseOutcome {
+    pub fn new(diagnostics: Vec<ParseDiagnostic>, base_url: Option<String>) -> Self {
+        Self {
+            items: Vec::new(),
+            diagnostics,
+            base_url,
+        }
+    }
+
+    /// Entries dropped because a required field was missing
+    pub fn skipped(&self) -> usize {
+        self.diagnostics.iter().filter(|d| d.skipped()).count()
+    }
+
+    /// Record entry `index` as skipped for lacking `field`
+    pub fn skip(&mut self, index: usize, byte_offset: Option<u64>, field: &str) {
+        self.warn(
+            index,
+            byte_offset,
+            DiagnosticKind::MissingField {
+                field: field.to_string(),
+            },
+        );
+    }
+
+    pub fn warn(&mut self, index: usize, byte_offset: Option<u64>, kind: DiagnosticKind) {
+        self.diagnostics.push(ParseDiagnostic {
+            item_index: Some(index),
+            byte_offset,
+            kind,
+        });
+    }
+
+    /// Keep the item of entry `index`, noting an unparseable `date`
+    pub fn push(&mut self, index: usize, byte_offset: Option<u64>, item: RssItem, date: &str) {
+        if item.published_at_fallback {
+            self.warn(
+                index,
+                byte_offset,
+                DiagnosticKind::InvalidDate {
+                    value: date.to_string(),
+                },
+            );
+        }
+        self.items.push(item);
+    }
+
+    /// Fail on the first warning (strict mode)
+    pub fn into_strict(mut self) -> RssResult<Self> {
+        if self.diagnostics.is_empty() {
+            return Ok(self);
+        }
+        self.diagnostics.sort_by_key(|d| d.item_index);
+        Err(RssParseError::Strict {
+            count: self.diagnostics.len(),
+            first: self.diagnostics[0].to_string(),
+        })
+    }
+}
+
+/// Syndication formats understood by `detect_and_parse`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedFormat {
+    Rss2,
+    /// RSS 1.0 / RDF Site Summary
+    Rss1,
+    Atom,
+    JsonFeed,
+}
+
+impl FeedFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Rss2 => "rss2",
+            Self::Rss1 => "rss1",
+            Self::Atom => "atom",
+            Self::JsonFeed => "json_feed",
+        }
+    }
+
+    pub fn parser(&self, source: &str) -> Box<dyn RssParser> {
+        match self {
+            Self::Rss2 | Self::Rss1 => Box::new(GenericRssParser::new(source)),
+            Self::Atom => Box::new(AtomParser::new(source)),
+            Self::JsonFeed => Box::new(JsonFeedParser::new(source)),
+        }
+    }
+}
+
+impl fmt::Display for FeedFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Sniff the payload: JSON objects are JSON Feed, XML is classified by its
+/// root element (`<rss>`, `<rdf:RDF>`, `<feed>`)
+pub fn detect_format(payload: &str) -> Option<FeedFormat> {
+    let trimmed = payload.trim_start_matches('\u{feff}').trim_start();
+    if trimmed.starts_with('{') {
+        return Some(FeedFormat::JsonFeed);
+    }
+
+    let mut reader = Reader::from_str(trimmed);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                return match e.local_name().as_ref() {
+                    b"rss" => Some(FeedFormat::Rss2),
+                    b"RDF" => Some(FeedFormat::Rss1),
+                    b"feed" => Some(FeedFormat::Atom),
+                    _ => None,
+                };
+            }
+            Ok(Event::Eof) | Err(_) => return None,
+            _ => {}
+        }
+    }
+}
+
+/// Detect the payload format and parse it with the matching generic parser
+pub fn detect_and_parse(payload: &str, source: &str) -> RssResult<(FeedFormat, Vec<RssItem>)> {
+    detect_and_parse_outcome(payload, source).map(|(format, outcome)| (format, outcome.items))
+}
+
+/// `detect_and_parse`, keeping the parse warnings
+pub fn detect_and_parse_outcome(
+    payload: &str,
+    source: &str,
+) -> RssResult<(FeedFormat, ParseOutcome)> {
+    let format = detect_format(payload).ok_or(RssParseError::UnknownFormat)?;
+    let outcome = format.parser(source).parse_outcome(payload)?;
+    Ok((format, outcome))
+}
+
+/// Parser that picks RSS 2.0, RSS 1.0, Atom or JSON Feed per payload
+#[derive(Debug, Clone)]
+pub struct AutoDetectParser {
+    source: String,
+}
+
+impl AutoDetectParser {
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+        }
+    }
+}
+
+impl RssParser for AutoDetectParser {
+    fn parse_outcome(&self, payload: &str) -> RssResult<ParseOutcome> {
+        detect_and_parse_outcome(payload, &self.source).map(|(_, outcome)| outcome)
+    }
 }
 
 // Utility functions
@@ -75,3 +427,52 @@ pub fn strip_cdata(text: &str) -> String {
         text.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undated_item_id_is_stable() {
+        let first = RssItem::new("src", "Title", "https://e.com/a", None, None);
+        let second = RssItem::new("src", "Title", "https://e.com/a", None, None);
+        assert_eq!(first.id, second.id);
+        assert!(first.published_at_fallback);
+
+        let other = RssItem::new("src", "Title", "https://e.com/b", None, None);
+        assert_ne!(first.id, other.id);
+    }
+
+    #[test]
+    fn test_detect_format() {
+        let rss2 = include_str!("../../tests/data/bloomberg_test.xml");
+        assert_eq!(detect_format(rss2), Some(FeedFormat::Rss2));
+
+        let rss1 = r#"<?xml version="1.0"?>
+<!-- RDF Site Summary -->
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns="http://purl.org/rss/1.0/"></rdf:RDF>"#;
+        assert_eq!(detect_format(rss1), Some(FeedFormat::Rss1));
+
+        let atom = "\u{feff}<?xml version=\"1.0\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\"/>";
+        assert_eq!(detect_format(atom), Some(FeedFormat::Atom));
+
+        let json = r#"  {"version": "https://jsonfeed.org/version/1.1", "items": []}"#;
+        assert_eq!(detect_format(json), Some(FeedFormat::JsonFeed));
+
+        assert_eq!(detect_format("<html><body/></html>"), None);
+        assert_eq!(detect_format("not a feed"), None);
+    }
+
+    #[test]
+    fn test_detect_and_parse() {
+        let rss2 = include_str!("../../tests/data/bloomberg_test.xml");
+        let (format, items) = detect_and_parse(rss2, "bloomberg").expect("Failed to parse");
+        assert_eq!(format, FeedFormat::Rss2);
+        assert!(!items.is_empty());
+
+        assert!(matches!(
+            detect_and_parse("<html/>", "x"),
+            Err(RssParseError::UnknownFormat)
+        ));
+    }
+}
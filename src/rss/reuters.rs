@@ -1,10 +1,6 @@
-use crate::error::RssParseError;
-use crate::rss::strip_cdata;
-use crate::rss::{RssItem, RssParser, RssResult};
+use crate::rss::generic::{collect_items, parse_channel};
+use crate::rss::{ParseOutcome, RssCategory, RssItem, RssParser, RssResult};
 use chrono::{DateTime, Utc};
-use quick_xml::Reader;
-use quick_xml::events::Event;
-use quick_xml::name::QName;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,8 +8,10 @@ pub struct ReutersRssItem {
     pub title: String,
     pub link: String,
     pub description: Option<String>,
-    pub published_at: DateTime<Utc>,
-    pub creator: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub creators: Vec<String>,
+    pub categories: Vec<RssCategory>,
+    pub guid: Option<String>,
 }
 
 impl ReutersRssItem {
@@ -23,105 +21,37 @@ impl ReutersRssItem {
             self.title,
             self.link,
             self.description,
-            Some(self.published_at),
+            self.published_at,
         )
+        .with_guid(self.guid)
+        .with_authors(self.creators)
+        .with_categories(self.categories)
     }
 }
 
 pub struct ReutersRssParser;
 
 impl RssParser for ReutersRssParser {
-    fn parse(&self, xml: &str) -> RssResult<Vec<RssItem>> {
-        let mut reader: Reader<&[u8]> = Reader::from_str(xml);
-
-        let mut buf = Vec::new();
-        let mut items = Vec::new();
-        let mut in_item = false;
-
-        // fields
-        let mut title: Option<String> = None;
-        let mut link: Option<String> = None;
-        let mut description: Option<String> = None;
-        let mut creator: Option<String> = None;
-        let mut published_at: Option<String> = None;
-
-        loop {
-            match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(ref e)) => match e.name().as_ref() {
-                    b"item" => {
-                        in_item = true;
-
-                        title = None;
-                        link = None;
-                        description = None;
-                        creator = None;
-                        published_at = None;
-                    }
-                    b"title" if in_item => {
-                        title = reader
-                            .read_text(QName(b"title"))
-                            .ok()
-                            .map(|s| strip_cdata(&s));
-                    }
-                    b"link" if in_item => {
-                        link = reader
-                            .read_text(QName(b"link"))
-                            .ok()
-                            .map(|s| s.into_owned());
-                    }
-                    b"description" if in_item => {
-                        description = reader
-                            .read_text(QName(b"description"))
-                            .ok()
-                            .map(|s| strip_cdata(&s));
-                    }
-                    b"pubDate" if in_item => {
-                        published_at = reader
-                            .read_text(QName(b"pubDate"))
-                            .ok()
-                            .map(|s| s.into_owned());
-                    }
-                    b"creator" if in_item => {
-                        creator = reader
-                            .read_text(QName(b"creator"))
-                            .ok()
-                            .map(|s| s.into_owned());
-                    }
-                    _ => {}
-                },
-
-                Ok(Event::End(ref e)) if e.name().as_ref() == b"item" => {
-                    in_item = false;
-
-                    if let (Some(title), Some(link), Some(pubdate)) =
-                        (title.clone(), link.clone(), published_at.clone())
-                    {
-                        // parse pubDate into chrono
-                        let parsed_dt = DateTime::parse_from_rfc2822(&pubdate)
-                            .map(|dt| dt.with_timezone(&Utc))
-                            .unwrap_or_else(|_| Utc::now());
-
-                        let item = ReutersRssItem {
-                            title,
-                            link,
-                            description: description.clone(),
-                            creator: creator.clone(),
-                            published_at: parsed_dt,
-                        };
-
-                        items.push(item.into_rss_item());
-                    }
-                }
-
-                Ok(Event::Eof) => break,
-
-                Err(e) => return Err(RssParseError::Xml(e.to_string())),
-
-                _ => {}
-            }
-        }
-
-        Ok(items)
+    fn parse_outcome(&self, xml: &str) -> RssResult<ParseOutcome> {
+        let channel = parse_channel(xml)?;
+
+        Ok(collect_items(channel, |item, title, link, published_at| {
+            let reuters_item = ReutersRssItem {
+                title,
+                link,
+                creators: item.authors(),
+                description: item.description,
+                categories: item
+                    .categories
+                    .into_iter()
+                    .map(|c| RssCategory::new(c.name, c.domain))
+                    .collect(),
+                published_at,
+                guid: item.guid.map(|g| g.value),
+            };
+
+            reuters_item.into_rss_item()
+        }))
     }
 }
 
@@ -0,0 +1,189 @@
+use crate::rss::html::to_plain_text;
+
+/// MinHash permutations per signature; similarity resolves to 1/32
+pub const MINHASH_SIZE: usize = 32;
+
+/// Words too common to say anything about a story
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "has", "have", "in", "is",
+    "it", "its", "of", "on", "or", "says", "that", "the", "to", "was", "were", "will", "with",
+];
+
+/// Near-duplicate fingerprint of an item's normalized title and summary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorySignature {
+    /// 64-bit SimHash over word frequencies
+    pub simhash: u64,
+    /// Minimum hash of the word bigrams under each of `MINHASH_SIZE`
+    /// permutations; empty for texts without words
+    pub minhash: Vec<u64>,
+}
+
+impl StorySignature {
+    /// Signature of `title` and `summary`; the summary may be HTML
+    pub fn new(title: &str, summary: Option<&str>) -> Self {
+        let mut text = title.to_string();
+        if let Some(summary) = summary {
+            text.push(' ');
+            text.push_str(&to_plain_text(summary, usize::MAX));
+        }
+        let words = words(&text);
+        Self {
+            simhash: simhash(&words),
+            minhash: minhash(&words),
+        }
+    }
+
+    /// Estimated Jaccard similarity of the two texts' word bigrams
+    pub fn similarity(&self, other: &Self) -> f64 {
+        if self.minhash.is_empty() || self.minhash.len() != other.minhash.len() {
+            return 0.0;
+        }
+        let equal = self
+            .minhash
+            .iter()
+            .zip(&other.minhash)
+            .filter(|(a, b)| a == b)
+            .count();
+        equal as f64 / self.minhash.len() as f64
+    }
+
+    /// Number of differing SimHash bits
+    pub fn distance(&self, other: &Self) -> u32 {
+        (self.simhash ^ other.simhash).count_ones()
+    }
+
+    /// MinHash values as big-endian bytes, for a `BYTEA` column
+    pub fn minhash_bytes(&self) -> Vec<u8> {
+        self.minhash.iter().flat_map(|h| h.to_be_bytes()).collect()
+    }
+
+    pub fn from_parts(simhash: u64, minhash_bytes: &[u8]) -> Self {
+        Self {
+            simhash,
+            minhash: minhash_bytes
+                .chunks_exact(8)
+                .map(|chunk| u64::from_be_bytes(chunk.try_into().expect("chunks of 8")))
+                .collect(),
+        }
+    }
+}
+
+/// Lowercase words without punctuation and stopwords
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .filter(|word| !STOPWORDS.contains(&word.as_str()))
+        .collect()
+}
+
+fn simhash(words: &[String]) -> u64 {
+    let mut weights = [0i64; 64];
+    for word in words {
+        let hash = fnv1a(word.as_bytes());
+        for (bit, weight) in weights.iter_mut().enumerate() {
+            *weight += if hash >> bit & 1 == 1 { 1 } else { -1 };
+        }
+    }
+    weights
+        .iter()
+        .enumerate()
+        .filter(|(_, weight)| **weight > 0)
+        .fold(0, |hash, (bit, _)| hash | 1 << bit)
+}
+
+fn minhash(words: &[String]) -> Vec<u64> {
+    let shingles: Vec<u64> = match words {
+        [] => return Vec::new(),
+        [word] => vec![fnv1a(word.as_bytes())],
+        _ => words
+            .windows(2)
+            .map(|pair| fnv1a(format!("{} {}", pair[0], pair[1]).as_bytes()))
+            .collect(),
+    };
+
+    let mut seed = 0x5eed_u64;
+    (0..MINHASH_SIZE)
+        .map(|_| {
+            seed = splitmix64(seed);
+            shingles
+                .iter()
+                .map(|&shingle| splitmix64(shingle ^ seed))
+                .min()
+                .expect("at least one shingle")
+        })
+        .collect()
+}
+
+/// FNV-1a: stable across builds, unlike `DefaultHasher`
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signatures_of_rewritten_stories() {
+        let bloomberg = StorySignature::new(
+            "Fed Holds Rates Steady, Signals Two Cuts Later This Year",
+            Some(
+                "<p>The Federal Reserve held interest rates steady on Wednesday and signaled two cuts later this year as inflation cools.</p>",
+            ),
+        );
+        let syndicated = StorySignature::new(
+            "Fed holds rates steady, signals two cuts later this year",
+            Some(
+                "The Federal Reserve held interest rates steady on Wednesday, and signaled two cuts later this year as inflation cools.",
+            ),
+        );
+        let unrelated = StorySignature::new(
+            "Bitcoin Tops $100,000 for the First Time",
+            Some("The largest cryptocurrency extended its rally after ETF inflows."),
+        );
+
+        // Case, markup and punctuation don't matter
+        assert_eq!(bloomberg, syndicated);
+        assert_eq!(bloomberg.similarity(&syndicated), 1.0);
+        assert_eq!(bloomberg.distance(&syndicated), 0);
+
+        assert!(bloomberg.similarity(&unrelated) < 0.2);
+        assert!(bloomberg.distance(&unrelated) > 10);
+
+        // One changed word keeps most bigrams
+        let edited = StorySignature::new(
+            "Fed Holds Rates Steady, Signals Three Cuts Later This Year",
+            Some(
+                "The Federal Reserve held interest rates steady on Wednesday and signaled three cuts later this year as inflation cools.",
+            ),
+        );
+        let similarity = bloomberg.similarity(&edited);
+        assert!(similarity > 0.5 && similarity < 1.0, "{similarity}");
+    }
+
+    #[test]
+    fn test_signature_round_trip_and_empty_text() {
+        let signature = StorySignature::new("Markets wrap", None);
+        assert_eq!(signature.minhash.len(), MINHASH_SIZE);
+        assert_eq!(
+            StorySignature::from_parts(signature.simhash, &signature.minhash_bytes()),
+            signature
+        );
+
+        // Nothing to compare: never similar, not even to itself
+        let empty = StorySignature::new("—", Some("<img src=\"x.png\">"));
+        assert!(empty.minhash.is_empty());
+        assert_eq!(empty.similarity(&empty), 0.0);
+    }
+}
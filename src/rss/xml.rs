@@ -0,0 +1,93 @@
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesCData, BytesRef, BytesStart, BytesText, Event};
+use quick_xml::name::ResolveResult;
+
+// Shared helpers for the quick-xml based parsers
+
+pub const DC_NS: &[u8] = b"http://purl.org/dc/elements/1.1/";
+pub const CONTENT_NS: &[u8] = b"http://purl.org/rss/1.0/modules/content/";
+pub const RSS1_NS: &[u8] = b"http://purl.org/rss/1.0/";
+pub const ATOM_NS: &[u8] = b"http://www.w3.org/2005/Atom";
+
+/// Namespace an element resolved to, falling back to well-known prefixes
+/// for feeds that use `dc:`, `content:` or `atom:` without declaring them.
+pub fn namespace_of<'a>(ns: &'a ResolveResult, prefix: Option<&'a [u8]>) -> Option<&'a [u8]> {
+    match ns {
+        ResolveResult::Bound(ns) => Some(ns.as_ref()),
+        ResolveResult::Unbound => None,
+        ResolveResult::Unknown(_) => match prefix {
+            Some(b"dc") => Some(DC_NS),
+            Some(b"content") => Some(CONTENT_NS),
+            Some(b"atom") => Some(ATOM_NS),
+            other => other,
+        },
+    }
+}
+
+/// Append the character data of a `Text`, `CData` or `GeneralRef` event.
+///
+/// Problems at this level don't stop parsing: undecodable text is dropped
+/// and unknown entities are kept verbatim, and the returned detail is
+/// reported as a diagnostic for the enclosing entry.
+pub fn push_content(buf: &mut String, event: &Event) -> Result<(), String> {
+    match event {
+        Event::Text(text) => push_text(buf, text),
+        Event::CData(cdata) => push_cdata(buf, cdata),
+        Event::GeneralRef(entity) => push_entity(buf, entity),
+        _ => Ok(()),
+    }
+}
+
+fn push_text(buf: &mut String, text: &BytesText) -> Result<(), String> {
+    let decoded = text.decode().map_err(|e| e.to_string())?;
+    buf.push_str(&decoded);
+    Ok(())
+}
+
+fn push_cdata(buf: &mut String, cdata: &BytesCData) -> Result<(), String> {
+    let decoded = cdata.decode().map_err(|e| e.to_string())?;
+    buf.push_str(&decoded);
+    Ok(())
+}
+
+/// Resolve `&amp;`, `&#8217;` and friends
+fn push_entity(buf: &mut String, entity: &BytesRef) -> Result<(), String> {
+    let char_ref = entity.resolve_char_ref();
+    if let Ok(Some(ch)) = char_ref {
+        buf.push(ch);
+        return Ok(());
+    }
+
+    let name = entity.decode().map_err(|e| e.to_string())?;
+    if let Some(value) = resolve_predefined_entity(&name) {
+        buf.push_str(value);
+        return Ok(());
+    }
+
+    buf.push('&');
+    buf.push_str(&name);
+    buf.push(';');
+    match char_ref {
+        Err(e) => Err(format!("invalid character reference '&{name};': {e}")),
+        Ok(_) => Err(format!("unknown entity '&{name};' kept verbatim")),
+    }
+}
+
+/// Unescaped value of the attribute with the given (qualified) name
+pub fn attribute(e: &BytesStart, key: &[u8]) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|attr| attr.key.as_ref() == key)
+        .map(|attr| match attr.unescape_value() {
+            Ok(value) => value.into_owned(),
+            Err(_) => String::from_utf8_lossy(attr.value.as_ref()).into_owned(),
+        })
+}
+
+/// Trimmed text, or `None` when only whitespace was collected
+pub fn take_text(buf: &mut String) -> Option<String> {
+    let text = buf.trim();
+    let result = (!text.is_empty()).then(|| text.to_string());
+    buf.clear();
+    result
+}